@@ -0,0 +1,75 @@
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static COLOR_MODE: AtomicU8 = AtomicU8::new(ColorMode::Auto as u8);
+static STDOUT_SUPPORTS_COLOR: OnceLock<bool> = OnceLock::new();
+static STDERR_SUPPORTS_COLOR: OnceLock<bool> = OnceLock::new();
+
+/// Sortie de la console.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Quand émettre les codes couleur ANSI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// Couleurs si la sortie est un terminal, en respectant `NO_COLOR` et `CLICOLOR_FORCE`.
+    #[default]
+    Auto = 0,
+    Always = 1,
+    Never = 2,
+}
+
+/// Choisit le mode couleur de toutes les macros qui produisent des codes ANSI.
+pub fn set_color_mode(mode: ColorMode) {
+    COLOR_MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+pub fn color_mode() -> ColorMode {
+    match COLOR_MODE.load(Ordering::Relaxed) {
+        1 => ColorMode::Always,
+        2 => ColorMode::Never,
+        _ => ColorMode::Auto,
+    }
+}
+
+/// `true` si la variable existe et n'est pas vide (convention `NO_COLOR`, même pour `NO_COLOR=0`).
+fn env_set(name: &str) -> bool {
+    std::env::var_os(name).is_some_and(|v| !v.is_empty())
+}
+
+/// `true` si la variable existe, n'est pas vide et ne vaut pas `0` (convention `CLICOLOR_FORCE`).
+fn env_flag(name: &str) -> bool {
+    std::env::var_os(name).is_some_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Décision du mode `Auto` pour une sortie dont on sait si elle est un terminal.
+pub(crate) fn auto_colors(is_terminal: bool) -> bool {
+    if env_set("NO_COLOR") {
+        return false;
+    }
+    if env_flag("CLICOLOR_FORCE") {
+        return true;
+    }
+    is_terminal
+}
+
+/// `true` si les couleurs sont actives pour la sortie standard.
+pub fn colors_enabled() -> bool {
+    colors_enabled_for(Stream::Stdout)
+}
+
+/// `true` si les couleurs sont actives pour `stream`.
+pub fn colors_enabled_for(stream: Stream) -> bool {
+    match color_mode() {
+        ColorMode::Always => true,
+        ColorMode::Never => false,
+        ColorMode::Auto => match stream {
+            Stream::Stdout => *STDOUT_SUPPORTS_COLOR.get_or_init(|| auto_colors(std::io::stdout().is_terminal())),
+            Stream::Stderr => *STDERR_SUPPORTS_COLOR.get_or_init(|| auto_colors(std::io::stderr().is_terminal())),
+        },
+    }
+}
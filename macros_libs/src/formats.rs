@@ -65,6 +65,7 @@ macro_rules! fmt_boxed {
 }
 
 /// Applique un style ANSI `$style` (codes couleur/format terminal) sur une chaîne formatée.
+/// Le style est omis quand les couleurs sont désactivées (voir `color::set_color_mode`).
 ///
 /// # Exemple
 /// ```
@@ -74,7 +75,11 @@ macro_rules! fmt_boxed {
 #[macro_export]
 macro_rules! styled_format {
     ($style:expr, $($arg:tt)*) => {{
-        format!("\x1b[{}m{}\x1b[0m", $style, format!($($arg)*))
+        if $crate::color::colors_enabled() {
+            format!("\x1b[{}m{}\x1b[0m", $style, format!($($arg)*))
+        } else {
+            format!($($arg)*)
+        }
     }};
 }
 
@@ -1,5 +1,8 @@
 #![crate_type = "proc-macro"]
 
+#[cfg(any(feature = "loggings", feature = "formats"))]
+pub mod color;
+
 #[macro_use]
 #[cfg(feature = "loggings")]
 pub mod logging;
@@ -1,103 +1,783 @@
-use std::fs::OpenOptions;
+use std::error::Error;
 use std::fmt::Display;
-use std::io::{Seek, SeekFrom, Write};
-use std::sync::OnceLock;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Once, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::thread;
+use std::time::Duration;
 
-static SAVE_LOG_TO_FILE: AtomicBool = AtomicBool::new(false);
-static LOG_FILE_PATH: OnceLock<String> = OnceLock::new();
-static MAX_LOG_FILE_SIZE: OnceLock<u64> = OnceLock::new();
-static LOG_LEVEL_FILTER: OnceLock<Level> = OnceLock::new();
+mod capture;
+#[cfg(feature = "compression")]
+mod compress;
+mod context;
+mod env_config;
+mod file;
+mod filter;
+mod format;
+#[cfg(feature = "log-compat")]
+mod log_compat;
+mod network;
+mod panic;
+mod pattern;
+mod ratelimit;
+#[cfg(feature = "redaction")]
+mod redact;
+mod rotation;
+mod sink;
+mod syslog;
+#[cfg(feature = "tracing-layer")]
+mod tracing_layer;
+mod worker;
 
+pub use crate::color::{ColorMode, Stream, set_color_mode};
+#[doc(hidden)]
+pub use capture::assert_logged;
+pub use capture::capture_logs;
+pub use context::{LogContextGuard, current_log_context, push_log_context};
+pub use env_config::{
+    ENV_LOG_BACKUPS, ENV_LOG_COLOR, ENV_LOG_CONSOLE_FORMAT, ENV_LOG_FILE, ENV_LOG_FORMAT, ENV_LOG_LEVEL, ENV_LOG_MAX_SIZE,
+    ENV_LOG_ROTATION, LoggerEnvError, init_logger_from_env,
+};
+pub use filter::{LogFilterError, ModuleFilter};
+pub use format::{LogFormat, RecordMetadata, format_record, format_record_with};
+#[cfg(feature = "log-compat")]
+pub use log_compat::init_log_compat;
+pub use network::{
+    DEFAULT_MAX_RECONNECT_DELAY, DEFAULT_NETWORK_BUFFER, DEFAULT_RECONNECT_DELAY, NetworkSink, Protocol,
+};
+pub use panic::{PanicLoggerOptions, install_panic_logger, install_panic_logger_with};
+pub use pattern::{LogPattern, PatternError};
+#[doc(hidden)]
+pub use ratelimit::{EveryN, Throttle};
+#[cfg(feature = "redaction")]
+pub use redact::{DEFAULT_REDACTED_FIELDS, REDACTED, Redaction};
+pub use rotation::{DEFAULT_MAX_LOG_BACKUPS, Rotation, RotationClock};
+pub use sink::{
+    CONSOLE_SINK, ConsoleSink, FILE_SINK, FileSink, LogSink, MemorySink, SinkId, add_sink, remove_sink,
+    set_sink_dedup, set_sink_format, set_sink_level, set_sink_metadata,
+};
+#[cfg(unix)]
+pub use syslog::SYSLOG_SOCKET;
+pub use syslog::{Facility, SyslogFormat, SyslogSink, syslog_severity};
+#[cfg(feature = "tracing-layer")]
+pub use tracing_layer::MacrosLayer;
+pub use worker::{AsyncLogGuard, OverflowPolicy, enable_async_logging};
+
+static LOGGER_CONFIG: RwLock<LoggerConfig> = RwLock::new(LoggerConfig::new());
+static PERIODIC_FLUSH: Once = Once::new();
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static THREAD_ID: u64 = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Intervalle de vidage par défaut du tampon du fichier de log.
+pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+/// Code de sortie par défaut de `log_fatal!`.
+pub const DEFAULT_FATAL_EXIT_CODE: i32 = 1;
+
+/// Niveaux de log, du plus bavard au plus grave : l'ordre sert aux filtres.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Level{
+    Trace,
     Debug,
     Info,
+    Success,
     Warn,
     Error,
-    Success,
+    Fatal,
 }
 
 impl Display for Level{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Level::Trace => write!(f, "Trace"),
             Level::Debug => write!(f, "Debug"),
             Level::Info => write!(f, "Info"),
+            Level::Success => write!(f, "Success"),
             Level::Warn => write!(f, "Warn"),
             Level::Error => write!(f, "Error"),
-            Level::Success => write!(f, "Success"),
+            Level::Fatal => write!(f, "Fatal"),
+        }
+    }
+}
+
+/// Nom de niveau inconnu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLevelError {
+    pub value: String,
+}
+
+impl Display for ParseLevelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "niveau de log inconnu : {:?}", self.value)
+    }
+}
+
+impl Error for ParseLevelError {}
+
+/// Accepte `"trace"`, `"debug"`, `"info"`, `"success"`, `"warn"` (ou `"warning"`),
+/// `"error"` et `"fatal"` (ou `"critical"`), sans tenir compte de la casse.
+impl std::str::FromStr for Level {
+    type Err = ParseLevelError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "trace" => Ok(Level::Trace),
+            "debug" => Ok(Level::Debug),
+            "info" => Ok(Level::Info),
+            "success" => Ok(Level::Success),
+            "warn" | "warning" => Ok(Level::Warn),
+            "error" => Ok(Level::Error),
+            "fatal" | "critical" => Ok(Level::Fatal),
+            _ => Err(ParseLevelError { value: value.to_string() }),
+        }
+    }
+}
+
+impl Level {
+    /// Nom du niveau en minuscules, utilisé par les formats structurés.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Level::Trace => "trace",
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Success => "success",
+            Level::Warn => "warn",
+            Level::Error => "error",
+            Level::Fatal => "fatal",
         }
     }
+
+    /// Code couleur ANSI utilisé par la macro de ce niveau.
+    pub fn color(&self) -> &'static str {
+        match self {
+            Level::Trace => "2;90",
+            Level::Debug => "90",
+            Level::Info => "34",
+            Level::Success => "32",
+            Level::Warn => "33",
+            Level::Error => "31",
+            Level::Fatal => "1;31",
+        }
+    }
+}
+
+/// Valeur typée d'un champ structuré attaché à un log.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Str(String),
+}
+
+impl Display for FieldValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldValue::Bool(v) => write!(f, "{}", v),
+            FieldValue::I64(v) => write!(f, "{}", v),
+            FieldValue::U64(v) => write!(f, "{}", v),
+            FieldValue::F64(v) => write!(f, "{}", v),
+            FieldValue::Str(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+macro_rules! field_value_from {
+    ($variant:ident($target:ty): $($t:ty),*) => {
+        $(
+            impl From<$t> for FieldValue {
+                fn from(v: $t) -> Self {
+                    FieldValue::$variant(v as $target)
+                }
+            }
+        )*
+    };
+}
+
+field_value_from!(I64(i64): i8, i16, i32, i64, isize);
+field_value_from!(U64(u64): u8, u16, u32, u64, usize);
+field_value_from!(F64(f64): f32, f64);
+
+impl From<bool> for FieldValue {
+    fn from(v: bool) -> Self {
+        FieldValue::Bool(v)
+    }
+}
+
+impl From<char> for FieldValue {
+    fn from(v: char) -> Self {
+        FieldValue::Str(v.to_string())
+    }
+}
+
+impl From<String> for FieldValue {
+    fn from(v: String) -> Self {
+        FieldValue::Str(v)
+    }
+}
+
+/// Toute référence vers un type affichable (`&str`, `&String`, `&SocketAddr`...) devient une chaîne.
+impl<T: Display + ?Sized> From<&T> for FieldValue {
+    fn from(v: &T) -> Self {
+        FieldValue::Str(v.to_string())
+    }
+}
+
+/// Champ clé/valeur attaché à un log, par exemple `user_id = 42`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub key: &'static str,
+    pub value: FieldValue,
+}
+
+impl Field {
+    pub fn new(key: &'static str, value: impl Into<FieldValue>) -> Self {
+        Self { key, value: value.into() }
+    }
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.value {
+            FieldValue::Str(v) if v.is_empty() || v.contains([' ', '=', '"']) => {
+                write!(f, "{}={:?}", self.key, v)
+            }
+            value => write!(f, "{}={}", self.key, value),
+        }
+    }
+}
+
+/// Emplacement dans le code d'un appel de log.
+#[derive(Debug, Clone, Copy)]
+pub struct CallSite {
+    pub module: &'static str,
+    pub file: &'static str,
+    pub line: u32,
+}
+
+/// Un message de log complet, avant mise en forme : niveau, message, champs,
+/// emplacement dans le code et thread d'origine.
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub level: Level,
+    pub time: chrono::DateTime<chrono::Local>,
+    pub message: String,
+    pub module: &'static str,
+    pub file: &'static str,
+    pub line: u32,
+    pub fields: Vec<Field>,
+    /// Code couleur ANSI du niveau (`"34"` pour Info...).
+    pub color: &'static str,
+    /// Nom du thread qui a émis le log.
+    pub thread: Option<String>,
+    /// Identifiant du thread, attribué par macros_libs dans l'ordre du premier log (1, 2...).
+    pub thread_id: u64,
+}
+
+impl LogRecord {
+    /// Crée un enregistrement daté de maintenant, pour le thread courant, avec les champs
+    /// de son contexte (`log_context!`).
+    pub fn new(level: Level, message: String, fields: Vec<Field>, site: CallSite) -> Self {
+        Self {
+            level,
+            time: chrono::Local::now(),
+            message,
+            module: site.module,
+            file: site.file,
+            line: site.line,
+            fields: context::merge_context(fields),
+            color: level.color(),
+            thread: thread::current().name().map(str::to_string),
+            thread_id: THREAD_ID.with(|id| *id),
+        }
+    }
+
+    /// Nom du thread, ou `thread-<id>` s'il n'en a pas.
+    pub fn thread_display(&self) -> String {
+        match &self.thread {
+            Some(name) => name.clone(),
+            None => format!("thread-{}", self.thread_id),
+        }
+    }
+}
+
+/// Options d'un fichier de log : chemin, rotation et rétention.
+#[derive(Debug, Clone)]
+pub struct FileOptions {
+    /// Fichier de sauvegarde des logs, `None` si la sauvegarde est désactivée.
+    pub path: Option<String>,
+    /// Taille maximale du fichier de log en octets avant rotation.
+    pub max_size: Option<u64>,
+    /// Nombre de fichiers de sauvegarde conservés (`app.log.1`, `app.log.2`...).
+    pub max_backups: usize,
+    /// Rotation temporelle : le fichier actif devient `app-2026-10-18.log`.
+    pub rotation: Rotation,
+    /// Horloge (locale ou UTC) qui détermine le changement de période.
+    pub rotation_clock: RotationClock,
+    /// Durée de conservation des fichiers datés, `None` pour tout garder.
+    pub retention: Option<Duration>,
+    /// Compresse en `.gz` les fichiers sortis de rotation.
+    #[cfg(feature = "compression")]
+    pub compress_rotated: bool,
+}
+
+impl FileOptions {
+    pub const fn new() -> Self {
+        Self {
+            path: None,
+            max_size: None,
+            max_backups: DEFAULT_MAX_LOG_BACKUPS,
+            rotation: Rotation::Never,
+            rotation_clock: RotationClock::Local,
+            retention: None,
+            #[cfg(feature = "compression")]
+            compress_rotated: false,
+        }
+    }
+}
+
+impl Default for FileOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Configuration courante du logger, modifiable à tout moment.
+#[derive(Debug, Clone)]
+pub struct LoggerConfig {
+    /// Options du fichier de log par défaut (`FILE_SINK`).
+    pub file: FileOptions,
+    /// Niveau minimal des logs affichés.
+    pub level_filter: Option<Level>,
+    /// Niveaux propres à certains modules, prioritaires sur `level_filter`.
+    pub module_filters: Vec<ModuleFilter>,
+    /// Sorties console choisies explicitement pour certains niveaux (voir `set_level_stream`).
+    pub console_streams: Vec<(Level, Stream)>,
+    /// Niveau à partir duquel chaque log vide immédiatement les tampons des sinks.
+    pub flush_level: Option<Level>,
+    /// Intervalle maximal entre deux vidages des tampons, `None` pour ne vider qu'à la demande.
+    pub flush_interval: Option<Duration>,
+    /// Code de sortie du processus après `log_fatal!`.
+    pub fatal_exit_code: i32,
+    /// Masquage des secrets, `None` s'il est désactivé.
+    #[cfg(feature = "redaction")]
+    pub redaction: Option<Redaction>,
+}
+
+impl LoggerConfig {
+    pub const fn new() -> Self {
+        Self {
+            file: FileOptions::new(),
+            level_filter: None,
+            module_filters: Vec::new(),
+            console_streams: Vec::new(),
+            flush_level: Some(Level::Error),
+            flush_interval: Some(DEFAULT_FLUSH_INTERVAL),
+            fatal_exit_code: DEFAULT_FATAL_EXIT_CODE,
+            #[cfg(feature = "redaction")]
+            redaction: None,
+        }
+    }
+}
+
+impl Default for LoggerConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn config() -> RwLockReadGuard<'static, LoggerConfig> {
+    LOGGER_CONFIG.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn config_mut() -> RwLockWriteGuard<'static, LoggerConfig> {
+    LOGGER_CONFIG.write().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Retourne une copie de la configuration courante.
+pub fn logger_config() -> LoggerConfig {
+    config().clone()
+}
+
+/// Remplace toute la configuration du logger.
+pub fn set_logger_config(new: LoggerConfig) {
+    *config_mut() = new;
 }
 
-#[allow(dead_code)]
 pub fn enable_log_saving(path: &str) {
-    LOG_FILE_PATH.set(path.to_string()).ok();
-    SAVE_LOG_TO_FILE.store(true, Ordering::SeqCst);
+    config_mut().file.path = Some(path.to_string());
+}
+
+pub fn disable_log_saving() {
+    config_mut().file.path = None;
+    sink::DEFAULT_FILE_SINK.close();
 }
 
 pub fn set_max_log_file_size(bytes: u64) {
-    MAX_LOG_FILE_SIZE.set(bytes).ok();
+    config_mut().file.max_size = Some(bytes);
+}
+
+/// Nombre de sauvegardes gardées lors de la rotation (0 : l'ancien fichier est supprimé).
+pub fn set_max_log_backups(count: usize) {
+    config_mut().file.max_backups = count;
+}
+
+pub fn set_log_rotation(rotation: Rotation) {
+    config_mut().file.rotation = rotation;
+}
+
+pub fn set_log_rotation_clock(clock: RotationClock) {
+    config_mut().file.rotation_clock = clock;
+}
+
+/// Supprime les fichiers datés plus anciens que `retention` à chaque changement de période.
+pub fn set_log_retention(retention: Duration) {
+    config_mut().file.retention = Some(retention);
+}
+
+/// Active la compression gzip des fichiers sortis de rotation, sur un thread dédié.
+/// Le fichier actif n'est jamais compressé.
+#[cfg(feature = "compression")]
+pub fn set_log_compression(enabled: bool) {
+    config_mut().file.compress_rotated = enabled;
 }
 
 pub fn set_log_level_filter(level: Level) {
-    LOG_LEVEL_FILTER.set(level).ok();
+    config_mut().level_filter = Some(level);
 }
 
-#[doc(hidden)]
-#[allow(dead_code)]
-fn level_priority(level: &Level) -> usize {
-    match level {
-        Level::Error => 4,
-        Level::Warn => 3,
-        Level::Info => 2,
-        Level::Debug => 1,
-        Level::Success => 2,
+/// Niveau minimal pour `module` et ses sous-modules (`my_crate::net`).
+pub fn set_module_log_level(module: &str, level: Level) {
+    let mut config = config_mut();
+    config.module_filters.retain(|f| f.module != module);
+    config.module_filters.push(ModuleFilter { module: module.to_string(), level: Some(level) });
+}
+
+/// Applique des directives façon `RUST_LOG` : `"info,my_crate::net=debug,my_crate::db=off"`.
+///
+/// Le niveau sans module remplace le filtre global, les directives par module
+/// remplacent toutes les précédentes. Le module le plus long qui correspond l'emporte.
+pub fn set_log_filters(spec: &str) -> Result<(), LogFilterError> {
+    let directives = filter::parse_directives(spec)?;
+    let mut config = config_mut();
+    config.module_filters = directives.modules;
+    match directives.default {
+        // `off` global : le module racine (chemin vide) coupe tout ce qui n'est pas ciblé.
+        Some(None) => config.module_filters.push(ModuleFilter { module: String::new(), level: None }),
+        Some(level) => config.level_filter = level,
+        None => {}
     }
+    Ok(())
+}
+
+/// Lit les directives dans la variable d'environnement `var` (par exemple `"RUST_LOG"`).
+/// Ne change rien si la variable n'existe pas.
+pub fn set_log_filters_from_env(var: &str) -> Result<(), LogFilterError> {
+    match std::env::var(var) {
+        Ok(spec) => set_log_filters(&spec),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Envoie les logs de `level` vers `stream` dans la console.
+/// Par défaut, Warn, Error et Fatal vont sur stderr et les autres niveaux sur stdout.
+pub fn set_level_stream(level: Level, stream: Stream) {
+    let mut config = config_mut();
+    config.console_streams.retain(|(l, _)| *l != level);
+    config.console_streams.push((level, stream));
+}
+
+/// Sortie console utilisée pour `level`.
+pub fn console_stream(level: &Level) -> Stream {
+    let config = config();
+    match config.console_streams.iter().find(|(l, _)| l == level) {
+        Some((_, stream)) => *stream,
+        None => match level {
+            Level::Warn | Level::Error | Level::Fatal => Stream::Stderr,
+            _ => Stream::Stdout,
+        },
+    }
+}
+
+pub fn set_console_format(format: LogFormat) {
+    set_sink_format(CONSOLE_SINK, format);
+}
+
+pub fn set_log_file_format(format: LogFormat) {
+    set_sink_format(FILE_SINK, format);
+}
+
+/// Applique le même modèle de ligne à la console et au fichier par défaut.
+///
+/// # Exemple
+/// ```
+/// # use macros_libs::logging::*;
+/// # fn main() -> Result<(), PatternError> {
+/// set_log_pattern("{time:%H:%M:%S%.3f} {level:>7} {thread} {module}:{line} - {msg}")?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn set_log_pattern(pattern: &str) -> Result<(), PatternError> {
+    let format = LogFormat::Pattern(LogPattern::parse(pattern)?.into());
+    set_sink_format(CONSOLE_SINK, format.clone());
+    set_sink_format(FILE_SINK, format);
+    Ok(())
+}
+
+/// Vide les tampons dès qu'un log atteint `level` (par défaut `Level::Error`).
+pub fn set_log_flush_level(level: Level) {
+    config_mut().flush_level = Some(level);
+}
+
+/// Vide les tampons au plus tard toutes les `interval`, même sans nouveau log.
+pub fn set_log_flush_interval(interval: Duration) {
+    config_mut().flush_interval = Some(interval);
+}
+
+/// Code de sortie du processus après `log_fatal!` (1 par défaut).
+pub fn set_fatal_exit_code(code: i32) {
+    config_mut().fatal_exit_code = code;
+}
+
+/// Remplace les règles de masquage des secrets.
+#[cfg(feature = "redaction")]
+pub fn set_log_redaction(redaction: Redaction) {
+    config_mut().redaction = Some(redaction);
+}
+
+/// Masque les secrets courants (`Redaction::with_defaults`) dans tous les logs.
+#[cfg(feature = "redaction")]
+pub fn enable_log_redaction() {
+    set_log_redaction(Redaction::with_defaults());
+}
+
+#[cfg(feature = "redaction")]
+pub fn disable_log_redaction() {
+    config_mut().redaction = None;
+}
+
+/// Écrit les logs encore en tampon dans chaque sink. À appeler avant de quitter le programme.
+///
+/// Avec `enable_async_logging`, attend d'abord que les logs en file d'attente soient écrits.
+pub fn flush_logs() {
+    worker::wait_idle();
+    sink::flush_sinks();
+}
+
+/// Remet le logger dans son état initial (utile entre deux tests) : configuration et sinks par défaut.
+pub fn reset_logger() {
+    set_logger_config(LoggerConfig::new());
+    sink::reset_sinks();
+    sink::DEFAULT_FILE_SINK.close();
+}
+
+/// Lance le thread qui vide les tampons à intervalle régulier.
+fn start_periodic_flush() {
+    PERIODIC_FLUSH.call_once(|| {
+        thread::spawn(|| {
+            loop {
+                let interval = config().flush_interval;
+                thread::sleep(interval.unwrap_or(DEFAULT_FLUSH_INTERVAL));
+                // Pas d'attente de la file asynchrone : ce vidage ne fait que borner la latence.
+                if interval.is_some() {
+                    sink::flush_sinks();
+                }
+            }
+        });
+    });
 }
 
 #[doc(hidden)]
-#[allow(dead_code)]
-pub fn should_log(level: &Level) -> bool {
-    if let Some(filter) = LOG_LEVEL_FILTER.get() {
-        level_priority(level) >= level_priority(filter)
-    } else {
-        true
+pub fn should_log(level: &Level, module: &str) -> bool {
+    if capture::is_capturing() {
+        return true;
     }
+    let config = config();
+    let filter = match filter::find_module_filter(&config.module_filters, module) {
+        Some(module_filter) => match module_filter.level {
+            Some(level) => Some(level),
+            None => return false,
+        },
+        None => config.level_filter,
+    };
+    filter.is_none_or(|filter| *level >= filter)
 }
 
+/// Écrit une ligne déjà formatée dans le fichier de log par défaut.
 #[doc(hidden)]
 pub fn write_log_to_file(line: &str) {
-    if SAVE_LOG_TO_FILE.load(Ordering::SeqCst) {
-        if let Some(path) = LOG_FILE_PATH.get() {
-            if let Ok(mut file) = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .read(true)
-                .open(path)
-            {
-                if let Some(max) = MAX_LOG_FILE_SIZE.get() {
-                    if let Ok(metadata) = file.metadata() {
-                        if metadata.len() > *max {
-                            let _ = file.set_len(0);
-                            let _ = file.seek(SeekFrom::Start(0));
-                        }
-                    }
-                }
-                let _ = writeln!(file, "{}", line);
-            }
-        }
+    let _ = sink::DEFAULT_FILE_SINK.write_line(line);
+}
+
+#[doc(hidden)]
+pub fn log_message(level: &Level, color: &'static str, args: std::fmt::Arguments, fields: Vec<Field>, site: CallSite) {
+    let record = redact(LogRecord { color, ..LogRecord::new(*level, args.to_string(), fields, site) });
+    if let Some(record) = capture::capture(record) {
+        emit_record(record);
+    }
+}
+
+/// Masque les secrets de l'enregistrement si le masquage est actif.
+#[cfg(feature = "redaction")]
+fn redact(mut record: LogRecord) -> LogRecord {
+    if let Some(redaction) = &config().redaction {
+        redaction.apply(&mut record);
+    }
+    record
+}
+
+#[cfg(not(feature = "redaction"))]
+fn redact(record: LogRecord) -> LogRecord {
+    record
+}
+
+/// Envoie un enregistrement au thread asynchrone s'il est actif, sinon directement aux sinks.
+fn emit_record(record: LogRecord) {
+    if let Some(record) = worker::enqueue(record) {
+        output_record(&record);
     }
 }
 
+/// Transmet un enregistrement aux sinks, depuis l'appelant ou le thread asynchrone.
+fn output_record(record: &LogRecord) {
+    let (flush_level, periodic) = {
+        let config = config();
+        (config.flush_level, config.flush_interval.is_some())
+    };
+    if periodic {
+        start_periodic_flush();
+    }
+    sink::dispatch(record);
+    if flush_level.is_some_and(|flush| record.level >= flush) {
+        flush_logs();
+    }
+}
+
+/// Attend que les logs en attente soient écrits, vide les sinks et quitte le processus.
+#[doc(hidden)]
+pub fn fatal_exit() -> ! {
+    flush_logs();
+    let code = config().fatal_exit_code;
+    std::process::exit(code)
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_call_site {
+    () => {
+        $crate::logging::CallSite { module: module_path!(), file: file!(), line: line!() }
+    };
+}
+
 /// Affiche un message de log avec niveau et couleur, puis le sauvegarde si activé.
+///
+/// Des champs structurés peuvent suivre le message après un `;` :
+/// ```
+/// # use macros_libs::log_info;
+/// # let addr = "127.0.0.1";
+/// log_info!("login ok"; user_id = 42, ip = &addr);
+/// ```
 #[macro_export]
 macro_rules! log_with_level {
+    ($level:expr, $color:expr, $fmt:literal $(, $arg:expr)* ; $($key:ident = $value:expr),+ $(,)?) => {{
+        if $crate::logging::should_log($level, module_path!()) {
+            $crate::logging::log_message(
+                $level,
+                $color,
+                format_args!($fmt $(, $arg)*),
+                vec![$($crate::logging::Field::new(stringify!($key), $value)),+],
+                $crate::__log_call_site!(),
+            );
+        }
+    }};
     ($level:expr, $color:expr, $($arg:tt)*) => {{
-        if should_log($level) {
-            let now = chrono::Local::now();
-            let msg = format!("{} [{}] {}", now.format("%Y-%m-%d %H:%M:%S"), $level, format!($($arg)*));
-            println!("\x1b[{}m{}\x1b[0m", $color, msg);
-            write_log_to_file(&msg);
+        if $crate::logging::should_log($level, module_path!()) {
+            $crate::logging::log_message(
+                $level,
+                $color,
+                format_args!($($arg)*),
+                Vec::new(),
+                $crate::__log_call_site!(),
+            );
+        }
+    }};
+}
+
+/// Ajoute des champs à tous les logs du thread courant tant que la garde renvoyée existe.
+///
+/// # Exemple
+/// ```
+/// # use macros_libs::{log_context, log_info};
+/// # let (id, tenant) = (42, "acme");
+/// let _ctx = log_context!(request_id = id, tenant = tenant);
+/// log_info!("requête reçue"); // ... request_id=42 tenant=acme
+/// ```
+#[macro_export]
+macro_rules! log_context {
+    ($($key:ident = $value:expr),+ $(,)?) => {
+        $crate::logging::push_log_context(vec![$($crate::logging::Field::new(stringify!($key), $value)),+])
+    };
+}
+
+/// Vérifie qu'un log de `level` dont le message contient `pattern` figure dans `logs`
+/// (renvoyés par `capture_logs`). Sinon, panique en listant les logs capturés.
+#[macro_export]
+macro_rules! assert_logged {
+    ($logs:expr, $level:expr, $pattern:expr $(,)?) => {
+        $crate::logging::assert_logged(&$logs, $level, $pattern)
+    };
+}
+
+/// Log au niveau `$level`, une seule fois par emplacement dans le code.
+///
+/// # Exemple
+/// ```
+/// # use macros_libs::log_once;
+/// # use macros_libs::logging::Level;
+/// log_once!(Level::Warn, "configuration obsolète, voir la documentation");
+/// ```
+#[macro_export]
+macro_rules! log_once {
+    ($level:expr, $($arg:tt)+) => {{
+        static ONCE: std::sync::Once = std::sync::Once::new();
+        ONCE.call_once(|| {
+            let level = $level;
+            $crate::log_with_level!(&level, level.color(), $($arg)+);
+        });
+    }};
+}
+
+/// Log au niveau `$level` au premier passage puis tous les `$n` passages par cet emplacement.
+#[macro_export]
+macro_rules! log_every_n {
+    ($n:expr, $level:expr, $($arg:tt)+) => {{
+        static COUNTER: $crate::logging::EveryN = $crate::logging::EveryN::new();
+        if COUNTER.tick($n) {
+            let level = $level;
+            $crate::log_with_level!(&level, level.color(), $($arg)+);
+        }
+    }};
+}
+
+/// Log au niveau `$level` au plus une fois par `$interval` (`Duration`) pour cet emplacement.
+///
+/// # Exemple
+/// ```
+/// # use std::time::Duration;
+/// # use macros_libs::log_throttled;
+/// # use macros_libs::logging::Level;
+/// # let err = "timeout";
+/// log_throttled!(Duration::from_secs(5), Level::Error, "base indisponible : {}", err);
+/// ```
+#[macro_export]
+macro_rules! log_throttled {
+    ($interval:expr, $level:expr, $($arg:tt)+) => {{
+        static THROTTLE: $crate::logging::Throttle = $crate::logging::Throttle::new();
+        if THROTTLE.ready($interval) {
+            let level = $level;
+            $crate::log_with_level!(&level, level.color(), $($arg)+);
         }
     }};
 }
@@ -106,7 +786,7 @@ macro_rules! log_with_level {
 #[macro_export]
 macro_rules! log_info {
     ($($arg:tt)*) => {
-        log_with_level!(&Level::Info, "34", $($arg)*);
+        $crate::log_with_level!(&$crate::logging::Level::Info, "34", $($arg)*);
     };
 }
 
@@ -114,7 +794,7 @@ macro_rules! log_info {
 #[macro_export]
 macro_rules! log_warn {
     ($($arg:tt)*) => {
-        log_with_level!(&Level::Warn, "33", $($arg)*);
+        $crate::log_with_level!(&$crate::logging::Level::Warn, "33", $($arg)*);
     };
 }
 
@@ -122,7 +802,7 @@ macro_rules! log_warn {
 #[macro_export]
 macro_rules! log_error {
     ($($arg:tt)*) => {
-        log_with_level!(&Level::Error, "31", $($arg)*);
+        $crate::log_with_level!(&$crate::logging::Level::Error, "31", $($arg)*);
     };
 }
 
@@ -131,14 +811,33 @@ macro_rules! log_error {
 macro_rules! log_debug {
     ($($arg:tt)*) => {
         #[cfg(debug_assertions)]
-        log_with_level!(&Level::Debug, "90", $($arg)*);
+        $crate::log_with_level!(&$crate::logging::Level::Debug, "90", $($arg)*);
     };
 }
 
+/// Log TRACE (gris pâle), plus bavard que DEBUG, actif uniquement en debug.
+#[macro_export]
+macro_rules! log_trace {
+    ($($arg:tt)*) => {
+        #[cfg(debug_assertions)]
+        $crate::log_with_level!(&$crate::logging::Level::Trace, "2;90", $($arg)*);
+    };
+}
+
+/// Log FATAL (rouge gras) : écrit le message, vide tous les sinks puis quitte le processus
+/// avec le code choisi par `set_fatal_exit_code`.
+#[macro_export]
+macro_rules! log_fatal {
+    ($($arg:tt)*) => {{
+        $crate::log_with_level!(&$crate::logging::Level::Fatal, "1;31", $($arg)*);
+        $crate::logging::fatal_exit()
+    }};
+}
+
 /// Log SUCCESS (vert).
 #[macro_export]
 macro_rules! log_success {
     ($($arg:tt)*) => {
-        log_with_level!(&Level::Success, "32", $($arg)*);
+        $crate::log_with_level!(&$crate::logging::Level::Success, "32", $($arg)*);
     };
 }
@@ -0,0 +1,70 @@
+use std::cell::RefCell;
+
+use super::{Level, LogRecord};
+
+thread_local! {
+    static CAPTURES: RefCell<Vec<Vec<LogRecord>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Retire la capture de la pile même si la closure panique.
+struct CaptureFrame;
+
+impl Drop for CaptureFrame {
+    fn drop(&mut self) {
+        CAPTURES.with(|captures| captures.borrow_mut().pop());
+    }
+}
+
+/// Exécute `f` et renvoie les logs émis par le thread courant pendant son exécution.
+///
+/// Les logs capturés ne passent ni par les filtres de niveau ni par les sinks : la sortie
+/// des tests reste propre et le résultat ne dépend pas de la configuration globale.
+///
+/// # Exemple
+/// ```
+/// # use macros_libs::{assert_logged, log_warn};
+/// # use macros_libs::logging::*;
+/// let logs = capture_logs(|| log_warn!("disque presque plein"; used = 93));
+/// assert_logged!(logs, Level::Warn, "presque plein");
+/// ```
+pub fn capture_logs(f: impl FnOnce()) -> Vec<LogRecord> {
+    CAPTURES.with(|captures| captures.borrow_mut().push(Vec::new()));
+    let frame = CaptureFrame;
+    f();
+    let logs = CAPTURES.with(|captures| captures.borrow_mut().last_mut().map(std::mem::take));
+    drop(frame);
+    logs.unwrap_or_default()
+}
+
+/// `true` si une capture est active sur le thread courant.
+pub(crate) fn is_capturing() -> bool {
+    CAPTURES.with(|captures| !captures.borrow().is_empty())
+}
+
+/// Garde `record` dans la capture la plus récente, ou le rend s'il n'y en a pas.
+pub(crate) fn capture(record: LogRecord) -> Option<LogRecord> {
+    CAPTURES.with(|captures| match captures.borrow_mut().last_mut() {
+        Some(logs) => {
+            logs.push(record);
+            None
+        }
+        None => Some(record),
+    })
+}
+
+/// Panique si aucun enregistrement de `level` ne contient `pattern` dans son message.
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_logged(logs: &[LogRecord], level: Level, pattern: &str) {
+    if logs.iter().any(|r| r.level == level && r.message.contains(pattern)) {
+        return;
+    }
+    let mut message = format!("aucun log {} contenant {:?} parmi {} capturé(s)", level, pattern, logs.len());
+    for record in logs {
+        message.push_str(&format!("\n  [{}] {}", record.level, record.message));
+        for field in &record.fields {
+            message.push_str(&format!(" {}", field));
+        }
+    }
+    panic!("{}", message);
+}
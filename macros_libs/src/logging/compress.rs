@@ -0,0 +1,113 @@
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex, MutexGuard, Once};
+use std::thread;
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+static JOBS: Mutex<Jobs> = Mutex::new(Jobs { queue: VecDeque::new(), active: None });
+static JOBS_READY: Condvar = Condvar::new();
+static COMPRESSOR: Once = Once::new();
+
+/// Fichiers à compresser, suivis sous leur nom courant : la rotation par taille les renomme
+/// (`app.log.1` -> `app.log.2`) pendant qu'ils attendent ou qu'ils sont compressés.
+pub(crate) struct Jobs {
+    queue: VecDeque<PathBuf>,
+    /// Fichier en cours de compression, `None` s'il a été supprimé entre-temps.
+    active: Option<PathBuf>,
+}
+
+impl Jobs {
+    /// À appeler après chaque renommage d'une sauvegarde.
+    pub(crate) fn renamed(&mut self, from: &Path, to: &Path) {
+        for path in self.queue.iter_mut().chain(self.active.as_mut()) {
+            if path == from {
+                *path = to.to_path_buf();
+            }
+        }
+    }
+
+    /// À appeler après la suppression d'une sauvegarde.
+    pub(crate) fn removed(&mut self, path: &Path) {
+        self.queue.retain(|p| p != path);
+        if self.active.as_deref() == Some(path) {
+            self.active = None;
+        }
+    }
+}
+
+/// Verrouille la liste des compressions. Tenu pendant le décalage des sauvegardes et pendant
+/// le renommage final d'une compression, jamais pendant la compression elle-même.
+pub(crate) fn jobs() -> MutexGuard<'static, Jobs> {
+    JOBS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Chemin compressé de `path` (`app.log.1` -> `app.log.1.gz`).
+pub(crate) fn gz_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".gz");
+    PathBuf::from(name)
+}
+
+/// Confie des fichiers déjà sortis de rotation au thread de compression.
+pub(crate) fn compress_in_background(paths: Vec<PathBuf>) {
+    COMPRESSOR.call_once(|| {
+        thread::Builder::new()
+            .name("macros_libs-gzip".to_string())
+            .spawn(run_compressor)
+            .expect("impossible de lancer le thread de compression");
+    });
+    let mut jobs = jobs();
+    for path in paths {
+        if !jobs.queue.contains(&path) && jobs.active.as_ref() != Some(&path) {
+            jobs.queue.push_back(path);
+        }
+    }
+    JOBS_READY.notify_one();
+}
+
+fn run_compressor() {
+    let mut counter = 0u64;
+    loop {
+        let (path, input) = {
+            let mut jobs = jobs();
+            loop {
+                match jobs.queue.pop_front() {
+                    Some(path) => match File::open(&path) {
+                        // Ouvert sous le verrou : le fichier reste lisible même s'il est renommé ensuite.
+                        Ok(input) => {
+                            jobs.active = Some(path.clone());
+                            break (path, input);
+                        }
+                        Err(_) => continue,
+                    },
+                    None => jobs = JOBS_READY.wait(jobs).unwrap_or_else(|e| e.into_inner()),
+                }
+            }
+        };
+        counter += 1;
+        let mut tmp = gz_path(&path).into_os_string();
+        tmp.push(format!(".{}.tmp", counter));
+        let tmp = PathBuf::from(tmp);
+        let compressed = compress_file(input, &tmp);
+
+        // Le fichier a pu être renommé ou supprimé pendant la compression : on suit son nom courant.
+        let mut jobs = jobs();
+        let placed = match (compressed, jobs.active.take()) {
+            (Ok(()), Some(current)) => fs::rename(&tmp, gz_path(&current)).ok().map(|()| current),
+            _ => None,
+        };
+        let _ = fs::remove_file(placed.as_deref().unwrap_or(&tmp));
+    }
+}
+
+/// Écrit le contenu de `input` compressé dans `target`.
+fn compress_file(input: File, target: &Path) -> io::Result<()> {
+    let mut input = BufReader::new(input);
+    let mut encoder = GzEncoder::new(BufWriter::new(File::create(target)?), Compression::default());
+    io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?.into_inner().map_err(|e| e.into_error())?.sync_all()
+}
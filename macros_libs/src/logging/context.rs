@@ -0,0 +1,61 @@
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+use super::Field;
+
+thread_local! {
+    static CONTEXT: RefCell<Vec<Field>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Garde d'un contexte de log : ses champs sont retirés du thread à sa destruction.
+///
+/// Les gardes doivent être détruites dans l'ordre inverse de leur création, ce que
+/// garantissent des variables locales dans des blocs imbriqués.
+#[must_use = "le contexte est retiré dès que la garde est détruite"]
+pub struct LogContextGuard {
+    len: usize,
+    // Le contexte appartient au thread courant.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for LogContextGuard {
+    fn drop(&mut self) {
+        CONTEXT.with(|context| context.borrow_mut().truncate(self.len));
+    }
+}
+
+/// Ajoute `fields` à chaque log du thread courant jusqu'à la destruction de la garde.
+/// Voir `log_context!`.
+pub fn push_log_context(fields: Vec<Field>) -> LogContextGuard {
+    CONTEXT.with(|context| {
+        let mut context = context.borrow_mut();
+        let len = context.len();
+        context.extend(fields);
+        LogContextGuard { len, _not_send: PhantomData }
+    })
+}
+
+/// Champs du contexte courant, par exemple pour les reprendre dans un thread lancé.
+pub fn current_log_context() -> Vec<Field> {
+    CONTEXT.with(|context| context.borrow().clone())
+}
+
+/// Place les champs du contexte avant ceux du log. En cas de clé en double, le champ du log
+/// l'emporte, puis le contexte le plus récent.
+pub(crate) fn merge_context(fields: Vec<Field>) -> Vec<Field> {
+    CONTEXT.with(|context| {
+        let context = context.borrow();
+        if context.is_empty() {
+            return fields;
+        }
+        let mut merged = Vec::with_capacity(context.len() + fields.len());
+        for (i, field) in context.iter().enumerate() {
+            let shadowed = context[i + 1..].iter().chain(&fields).any(|f| f.key == field.key);
+            if !shadowed {
+                merged.push(field.clone());
+            }
+        }
+        merged.extend(fields);
+        merged
+    })
+}
@@ -0,0 +1,180 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+
+use crate::color::{ColorMode, set_color_mode};
+
+use super::{
+    LogFormat, Rotation, enable_log_saving, set_console_format, set_log_file_format, set_log_filters,
+    set_log_rotation, set_max_log_backups, set_max_log_file_size,
+};
+
+/// Directives de niveau, par exemple `info,my_crate::net=debug`. Un module sans niveau
+/// s'écrit avec son chemin complet (`my_crate::net`) ou `my_crate=trace`.
+pub const ENV_LOG_LEVEL: &str = "MACROS_LOG_LEVEL";
+/// Fichier de log à activer.
+pub const ENV_LOG_FILE: &str = "MACROS_LOG_FILE";
+/// Taille maximale du fichier avant rotation : `1048576`, `512K`, `10M`, `1G`.
+pub const ENV_LOG_MAX_SIZE: &str = "MACROS_LOG_MAX_SIZE";
+/// Nombre de sauvegardes conservées.
+pub const ENV_LOG_BACKUPS: &str = "MACROS_LOG_BACKUPS";
+/// Rotation temporelle : `never`, `hourly` ou `daily`.
+pub const ENV_LOG_ROTATION: &str = "MACROS_LOG_ROTATION";
+/// Format du fichier : `plain`, `json` ou `logfmt`.
+pub const ENV_LOG_FORMAT: &str = "MACROS_LOG_FORMAT";
+/// Format de la console : `plain`, `json` ou `logfmt`.
+pub const ENV_LOG_CONSOLE_FORMAT: &str = "MACROS_LOG_CONSOLE_FORMAT";
+
+/// Couleurs de la console : `auto`, `always` ou `never`.
+pub const ENV_LOG_COLOR: &str = "MACROS_LOG_COLOR";
+
+/// Variable d'environnement du logger avec une valeur invalide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggerEnvError {
+    pub var: &'static str,
+    pub value: String,
+    pub expected: &'static str,
+}
+
+impl fmt::Display for LoggerEnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={:?} invalide, attendu : {}", self.var, self.value, self.expected)
+    }
+}
+
+impl Error for LoggerEnvError {}
+
+fn parse_format(value: &str) -> Option<LogFormat> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "plain" | "text" => Some(LogFormat::Plain),
+        "json" | "jsonl" | "jsonlines" => Some(LogFormat::JsonLines),
+        "logfmt" => Some(LogFormat::Logfmt),
+        _ => None,
+    }
+}
+
+fn parse_color(value: &str) -> Option<ColorMode> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "auto" => Some(ColorMode::Auto),
+        "always" | "true" | "1" | "on" => Some(ColorMode::Always),
+        "never" | "false" | "0" | "off" => Some(ColorMode::Never),
+        _ => None,
+    }
+}
+
+fn parse_rotation(value: &str) -> Option<Rotation> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "never" | "none" => Some(Rotation::Never),
+        "hourly" => Some(Rotation::Hourly),
+        "daily" => Some(Rotation::Daily),
+        _ => None,
+    }
+}
+
+fn parse_size(value: &str) -> Option<u64> {
+    let value = value.trim().to_ascii_uppercase();
+    let value = value.strip_suffix('B').unwrap_or(&value);
+    let (digits, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => value.split_at(index),
+        None => (value, ""),
+    };
+    let multiplier = match unit.trim() {
+        "" => 1,
+        "K" => 1024,
+        "M" => 1024 * 1024,
+        "G" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    digits.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+/// Directives de `MACROS_LOG_LEVEL`. Un module seul doit contenir `::` : sinon `warnn` ou
+/// `verbose` deviendraient un filtre de module au lieu d'une erreur.
+fn is_valid_level_spec(value: &str) -> bool {
+    super::filter::parse_directives(value).is_ok()
+        && value
+            .split(',')
+            .map(str::trim)
+            .filter(|d| !d.is_empty() && !d.contains('=') && !d.contains("::"))
+            .all(|d| super::filter::parse_level(d).is_ok())
+}
+
+fn read<T>(
+    var: &'static str,
+    expected: &'static str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Option<T>, LoggerEnvError> {
+    match env::var(var) {
+        Ok(value) => parse(&value)
+            .map(Some)
+            .ok_or(LoggerEnvError { var, value, expected }),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Configure le logger depuis les variables `MACROS_LOG_*`, sans toucher au code.
+///
+/// Toutes les variables sont validées avant d'appliquer quoi que ce soit : à la première
+/// valeur invalide, la configuration reste inchangée et l'erreur indique la variable fautive.
+///
+/// # Exemple
+/// ```
+/// # use macros_libs::logging::init_logger_from_env;
+/// // MACROS_LOG_LEVEL=info,my_crate::db=warn MACROS_LOG_FILE=app.log MACROS_LOG_FORMAT=json
+/// init_logger_from_env().unwrap_or_else(|e| eprintln!("{}", e));
+/// ```
+pub fn init_logger_from_env() -> Result<(), LoggerEnvError> {
+    let level = read(
+        ENV_LOG_LEVEL,
+        "un niveau (trace, debug, info, success, warn, error, fatal, off) ou des directives module=niveau",
+        |v| is_valid_level_spec(v).then(|| v.to_string()),
+    )?;
+    let file = env::var(ENV_LOG_FILE).ok().filter(|path| !path.is_empty());
+    let max_size = read(ENV_LOG_MAX_SIZE, "une taille en octets (1048576, 512K, 10M, 1G)", parse_size)?;
+    let backups = read(ENV_LOG_BACKUPS, "un nombre entier", |v| v.trim().parse::<usize>().ok())?;
+    let rotation = read(ENV_LOG_ROTATION, "never, hourly ou daily", parse_rotation)?;
+    let format = read(ENV_LOG_FORMAT, "plain, json ou logfmt", parse_format)?;
+    let console_format = read(ENV_LOG_CONSOLE_FORMAT, "plain, json ou logfmt", parse_format)?;
+    let color = read(ENV_LOG_COLOR, "auto, always ou never", parse_color)?;
+
+    if let Some(level) = level {
+        let _ = set_log_filters(&level);
+    }
+    if let Some(bytes) = max_size {
+        set_max_log_file_size(bytes);
+    }
+    if let Some(count) = backups {
+        set_max_log_backups(count);
+    }
+    if let Some(rotation) = rotation {
+        set_log_rotation(rotation);
+    }
+    if let Some(format) = format {
+        set_log_file_format(format);
+    }
+    if let Some(format) = console_format {
+        set_console_format(format);
+    }
+    if let Some(mode) = color {
+        set_color_mode(mode);
+    }
+    if let Some(path) = file {
+        enable_log_saving(&path);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_spec_rejects_misspelled_levels() {
+        for valid in ["info", "WARN", "off", "info,my_crate::net=debug", "my_crate=trace", "my_crate::db"] {
+            assert!(is_valid_level_spec(valid), "{}", valid);
+        }
+        for invalid in ["warnn", "verbose", "inf", "info,debugg", "my_crate::net=loud"] {
+            assert!(!is_valid_level_spec(invalid), "{}", invalid);
+        }
+    }
+}
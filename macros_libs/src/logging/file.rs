@@ -0,0 +1,133 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::{FileOptions, rotation};
+
+/// Délai avant de retenter une rotation par taille qui a échoué.
+const ROTATION_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// Fichier de log ouvert une seule fois et écrit à travers un `BufWriter`.
+///
+/// Suit le fichier actif (rotation par taille ou par date) et le rouvre seulement
+/// quand le chemin change.
+#[derive(Debug)]
+pub(crate) struct FileWriter {
+    period: Option<String>,
+    path: Option<PathBuf>,
+    writer: Option<BufWriter<File>>,
+    size: u64,
+    /// Après un échec de rotation, pas de nouvel essai avant cet instant.
+    rotation_retry: Option<Instant>,
+}
+
+impl FileWriter {
+    pub(crate) const fn new() -> Self {
+        Self {
+            period: None,
+            path: None,
+            writer: None,
+            size: 0,
+            rotation_retry: None,
+        }
+    }
+
+    /// Écrit `line` dans le fichier configuré, en gérant rotation et rétention.
+    ///
+    /// Si la rotation échoue, la ligne est tout de même écrite dans le fichier courant et
+    /// l'erreur de rotation est renvoyée ; la rotation n'est retentée qu'après un délai.
+    pub(crate) fn write_line(&mut self, options: &FileOptions, line: &str) -> io::Result<()> {
+        let Some(base) = &options.path else {
+            return Ok(());
+        };
+        let base = Path::new(base);
+        let path = self.active_path(options, base);
+        if self.path.as_ref() != Some(&path) {
+            self.open(path)?;
+        }
+        let incoming = line.len() as u64 + 1;
+        let mut rotation_error = None;
+        if let Some(max) = options.max_size
+            && rotation::needs_size_rotation(self.size, incoming, max)
+            && self.rotation_retry.is_none_or(|retry| Instant::now() >= retry)
+        {
+            match self.rotate(options) {
+                Ok(()) => self.rotation_retry = None,
+                Err(err) => {
+                    self.rotation_retry = Some(Instant::now() + ROTATION_RETRY_DELAY);
+                    rotation_error = Some(err);
+                }
+            }
+        }
+        if let Some(writer) = &mut self.writer {
+            writeln!(writer, "{}", line)?;
+            self.size += incoming;
+        }
+        rotation_error.map_or(Ok(()), Err)
+    }
+
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        match &mut self.writer {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Vide le tampon et ferme le fichier, le prochain appel le rouvrira.
+    pub(crate) fn close(&mut self) {
+        let _ = self.flush();
+        *self = Self::new();
+    }
+
+    fn active_path(&mut self, options: &FileOptions, base: &Path) -> PathBuf {
+        let now = options.rotation_clock.now();
+        let Some(period) = options.rotation.period(now) else {
+            return base.to_path_buf();
+        };
+        let path = rotation::dated_path(base, &period);
+        if self.period.as_ref() != Some(&period) {
+            #[cfg(feature = "compression")]
+            if options.compress_rotated
+                && let Some(previous) = &self.period
+            {
+                let previous = rotation::dated_path(base, previous);
+                let _ = self.flush();
+                self.writer = None;
+                let mut rotated = vec![previous.clone()];
+                rotated.extend((1..=options.max_backups).map(|i| rotation::backup_path(&previous, i)));
+                super::compress::compress_in_background(rotated);
+            }
+            if let Some(retention) = options.retention {
+                let _ = rotation::remove_expired(base, options.rotation, now, retention);
+            }
+            self.period = Some(period);
+        }
+        path
+    }
+
+    fn open(&mut self, path: PathBuf) -> io::Result<()> {
+        let _ = self.flush();
+        self.writer = None;
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        self.size = file.metadata()?.len();
+        self.writer = Some(BufWriter::new(file));
+        self.path = Some(path);
+        Ok(())
+    }
+
+    fn rotate(&mut self, options: &FileOptions) -> io::Result<()> {
+        let Some(path) = self.path.take() else {
+            return Ok(());
+        };
+        let _ = self.flush();
+        self.writer = None;
+        let rotated = rotation::rotate_by_size(&path, options.max_backups);
+        #[cfg(feature = "compression")]
+        if rotated.is_ok() && options.compress_rotated && options.max_backups > 0 {
+            super::compress::compress_in_background(vec![rotation::backup_path(&path, 1)]);
+        }
+        let reopened = self.open(path);
+        rotated.and(reopened)
+    }
+}
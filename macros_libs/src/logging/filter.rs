@@ -0,0 +1,85 @@
+use std::error::Error;
+use std::fmt;
+
+use super::Level;
+
+/// Niveau imposé à un module et à ses sous-modules ; `level == None` coupe ses logs.
+/// Un module vide correspond à tous les modules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleFilter {
+    pub module: String,
+    pub level: Option<Level>,
+}
+
+/// Directive de filtre invalide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilterError {
+    pub directive: String,
+}
+
+impl fmt::Display for LogFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "directive de log invalide : {:?}", self.directive)
+    }
+}
+
+impl Error for LogFilterError {}
+
+/// Résultat de l'analyse d'une chaîne de directives.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Directives {
+    /// Niveau global (directive sans module), `Some(None)` pour `off`.
+    pub(crate) default: Option<Option<Level>>,
+    pub(crate) modules: Vec<ModuleFilter>,
+}
+
+/// Un niveau (`Level::from_str`) ou `off`.
+pub(crate) fn parse_level(value: &str) -> Result<Option<Level>, ()> {
+    if value.trim().eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    value.parse().map(Some).map_err(|_| ())
+}
+
+/// Analyse `"info,my_crate::net=debug,my_crate::db=warn"`.
+pub(crate) fn parse_directives(spec: &str) -> Result<Directives, LogFilterError> {
+    let mut directives = Directives::default();
+    for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+        let error = || LogFilterError { directive: directive.to_string() };
+        match directive.split_once('=') {
+            Some((module, level)) => {
+                let module = module.trim();
+                if module.is_empty() {
+                    return Err(error());
+                }
+                let level = parse_level(level).map_err(|_| error())?;
+                directives.modules.push(ModuleFilter { module: module.to_string(), level });
+            }
+            None => match parse_level(directive) {
+                Ok(level) => directives.default = Some(level),
+                // Un module seul, comme dans RUST_LOG, active tous ses logs.
+                Err(()) if is_module_path(directive) => {
+                    directives.modules.push(ModuleFilter { module: directive.to_string(), level: Some(Level::Trace) })
+                }
+                Err(()) => return Err(error()),
+            },
+        }
+    }
+    Ok(directives)
+}
+
+fn is_module_path(value: &str) -> bool {
+    value.split("::").all(|part| !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_'))
+}
+
+/// Filtre du module le plus spécifique qui contient `module`.
+pub(crate) fn find_module_filter<'a>(filters: &'a [ModuleFilter], module: &str) -> Option<&'a ModuleFilter> {
+    filters
+        .iter()
+        .filter(|f| {
+            f.module.is_empty()
+                || module == f.module
+                || module.strip_prefix(f.module.as_str()).is_some_and(|rest| rest.starts_with("::"))
+        })
+        .max_by_key(|f| f.module.len())
+}
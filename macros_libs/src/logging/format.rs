@@ -0,0 +1,188 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use super::{FieldValue, LogPattern, LogRecord};
+
+/// Métadonnées ajoutées par les formats `Plain`, `JsonLines` et `Logfmt`.
+///
+/// Par défaut, `Plain` n'en affiche aucune et les formats structurés les incluent toutes.
+/// Un `Pattern` choisit lui-même ses marqueurs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordMetadata {
+    /// Module, fichier et ligne de l'appel.
+    pub location: bool,
+    /// Nom et identifiant du thread.
+    pub thread: bool,
+}
+
+impl RecordMetadata {
+    pub const NONE: Self = Self { location: false, thread: false };
+    pub const ALL: Self = Self { location: true, thread: true };
+
+    /// Métadonnées par défaut de `format`.
+    pub fn default_for(format: &LogFormat) -> Self {
+        if format.is_structured() { Self::ALL } else { Self::NONE }
+    }
+}
+
+/// Clés écrites par les formats structurés. Un champ du même nom devient `fields.<nom>`
+/// pour ne pas produire de clé en double.
+const RESERVED_KEYS: &[&str] = &["ts", "level", "msg", "module", "file", "line", "thread", "thread_id"];
+
+/// Format de sortie d'une ligne de log.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// `2026-10-18 12:00:00 [Info] message clé=valeur`
+    #[default]
+    Plain,
+    /// Un objet JSON par ligne (`ts`, `level`, `msg`, `module`, `file`, `line` et les champs ;
+    /// un champ `msg` devient `fields.msg`).
+    JsonLines,
+    /// Paires `clé=valeur` au format logfmt.
+    Logfmt,
+    /// Modèle personnalisé, par exemple `"{time:%H:%M:%S} {level:>7} - {msg}"`.
+    Pattern(Arc<LogPattern>),
+}
+
+impl LogFormat {
+    /// `true` pour les formats destinés aux machines (JSON Lines, logfmt), jamais colorés.
+    pub fn is_structured(&self) -> bool {
+        matches!(self, LogFormat::JsonLines | LogFormat::Logfmt)
+    }
+}
+
+/// Formate un enregistrement selon `format`, sans couleur ni retour à la ligne final.
+pub fn format_record(record: &LogRecord, format: &LogFormat) -> String {
+    format_record_with(record, format, RecordMetadata::default_for(format))
+}
+
+/// Comme `format_record`, en choisissant les métadonnées incluses.
+pub fn format_record_with(record: &LogRecord, format: &LogFormat, metadata: RecordMetadata) -> String {
+    match format {
+        LogFormat::Plain => format_plain(record, metadata),
+        LogFormat::JsonLines => format_json(record, metadata),
+        LogFormat::Logfmt => format_logfmt(record, metadata),
+        LogFormat::Pattern(pattern) => pattern.format(record),
+    }
+}
+
+fn format_plain(record: &LogRecord, metadata: RecordMetadata) -> String {
+    let mut out = format!("{} [{}]", record.time.format("%Y-%m-%d %H:%M:%S"), record.level);
+    if metadata.thread {
+        let _ = write!(out, " [{}]", record.thread_display());
+    }
+    if metadata.location {
+        let _ = write!(out, " {} ({}:{})", record.module, record.file, record.line);
+    }
+    let _ = write!(out, " {}", record.message);
+    for field in &record.fields {
+        let _ = write!(out, " {}", field);
+    }
+    out
+}
+
+fn format_json(record: &LogRecord, metadata: RecordMetadata) -> String {
+    let mut out = String::from("{");
+    push_json_pair(&mut out, "ts", &timestamp(record));
+    out.push(',');
+    push_json_pair(&mut out, "level", record.level.as_str());
+    out.push(',');
+    push_json_pair(&mut out, "msg", &record.message);
+    if metadata.location {
+        out.push(',');
+        push_json_pair(&mut out, "module", record.module);
+        out.push(',');
+        push_json_pair(&mut out, "file", record.file);
+        let _ = write!(out, ",\"line\":{}", record.line);
+    }
+    if metadata.thread {
+        if let Some(name) = &record.thread {
+            out.push(',');
+            push_json_pair(&mut out, "thread", name);
+        }
+        let _ = write!(out, ",\"thread_id\":{}", record.thread_id);
+    }
+    for field in &record.fields {
+        out.push(',');
+        push_json_string(&mut out, &field_key(field.key));
+        out.push(':');
+        match &field.value {
+            FieldValue::Str(v) => push_json_string(&mut out, v),
+            FieldValue::F64(v) if !v.is_finite() => push_json_string(&mut out, &v.to_string()),
+            value => {
+                let _ = write!(out, "{}", value);
+            }
+        }
+    }
+    out.push('}');
+    out
+}
+
+fn format_logfmt(record: &LogRecord, metadata: RecordMetadata) -> String {
+    let mut out = String::new();
+    push_logfmt_pair(&mut out, "ts", &timestamp(record));
+    push_logfmt_pair(&mut out, "level", record.level.as_str());
+    push_logfmt_pair(&mut out, "msg", &record.message);
+    if metadata.location {
+        push_logfmt_pair(&mut out, "module", record.module);
+        push_logfmt_pair(&mut out, "file", record.file);
+        push_logfmt_pair(&mut out, "line", &record.line.to_string());
+    }
+    if metadata.thread {
+        if let Some(name) = &record.thread {
+            push_logfmt_pair(&mut out, "thread", name);
+        }
+        push_logfmt_pair(&mut out, "thread_id", &record.thread_id.to_string());
+    }
+    for field in &record.fields {
+        push_logfmt_pair(&mut out, &field_key(field.key), &field.value.to_string());
+    }
+    out
+}
+
+fn field_key(key: &str) -> Cow<'_, str> {
+    if RESERVED_KEYS.contains(&key) { Cow::Owned(format!("fields.{}", key)) } else { Cow::Borrowed(key) }
+}
+
+fn timestamp(record: &LogRecord) -> String {
+    record.time.format("%Y-%m-%dT%H:%M:%S%.3f%:z").to_string()
+}
+
+fn push_json_pair(out: &mut String, key: &str, value: &str) {
+    push_json_string(out, key);
+    out.push(':');
+    push_json_string(out, value);
+}
+
+/// Ajoute `value` entre guillemets en échappant les caractères spéciaux JSON.
+pub(crate) fn push_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn push_logfmt_pair(out: &mut String, key: &str, value: &str) {
+    if !out.is_empty() {
+        out.push(' ');
+    }
+    out.push_str(key);
+    out.push('=');
+    if value.is_empty() || value.contains([' ', '=', '"']) || value.contains(char::is_control) {
+        let _ = write!(out, "{:?}", value);
+    } else {
+        out.push_str(value);
+    }
+}
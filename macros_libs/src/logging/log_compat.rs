@@ -0,0 +1,54 @@
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use super::{CallSite, Level, flush_logs, log_message, should_log};
+
+static LOGGER: MacrosLogger = MacrosLogger;
+
+/// Implémentation de `log::Log` qui renvoie les enregistrements vers les sinks de macros_libs.
+struct MacrosLogger;
+
+impl From<log::Level> for Level {
+    fn from(level: log::Level) -> Self {
+        match level {
+            log::Level::Error => Level::Error,
+            log::Level::Warn => Level::Warn,
+            log::Level::Info => Level::Info,
+            log::Level::Debug => Level::Debug,
+            log::Level::Trace => Level::Trace,
+        }
+    }
+}
+
+impl Log for MacrosLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        should_log(&metadata.level().into(), metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        let level = Level::from(record.level());
+        let module = record.module_path().unwrap_or(record.target());
+        if !should_log(&level, module) {
+            return;
+        }
+        let site = CallSite {
+            module: record.module_path_static().unwrap_or("?"),
+            file: record.file_static().unwrap_or("?"),
+            line: record.line().unwrap_or(0),
+        };
+        log_message(&level, level.color(), *record.args(), Vec::new(), site);
+    }
+
+    fn flush(&self) {
+        flush_logs();
+    }
+}
+
+/// Installe macros_libs comme logger global de la façade `log` : les `log::info!` des
+/// dépendances passent par les mêmes filtres, couleurs et sinks que `log_info!`.
+///
+/// Échoue si un autre logger `log` est déjà installé.
+pub fn init_log_compat() -> Result<(), SetLoggerError> {
+    log::set_logger(&LOGGER)?;
+    log::set_max_level(LevelFilter::Trace);
+    Ok(())
+}
@@ -0,0 +1,385 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, Once};
+use std::thread;
+use std::time::Duration;
+
+use super::{LogFormat, LogRecord, LogSink};
+
+/// Nombre de lignes gardées par défaut pendant une coupure.
+pub const DEFAULT_NETWORK_BUFFER: usize = 10_000;
+/// Délai avant la première tentative de reconnexion, doublé à chaque échec.
+pub const DEFAULT_RECONNECT_DELAY: Duration = Duration::from_millis(100);
+/// Délai maximal entre deux tentatives de reconnexion.
+pub const DEFAULT_MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+/// Délai maximal d'une écriture TCP avant de retenter la suite de la ligne.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+/// Attente maximale de `flush` avant de rendre la main.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Transport d'un `NetworkSink`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// Une ligne par log sur une connexion persistante.
+    Tcp,
+    /// Un datagramme par log.
+    Udp,
+}
+
+enum Connection {
+    Tcp(TcpStream),
+    Udp(UdpSocket),
+}
+
+impl Connection {
+    fn open(protocol: Protocol, addr: SocketAddr) -> io::Result<Self> {
+        match protocol {
+            Protocol::Tcp => {
+                let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+                stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+                stream.set_nodelay(true)?;
+                Ok(Connection::Tcp(stream))
+            }
+            Protocol::Udp => {
+                let socket = UdpSocket::bind(if addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" })?;
+                socket.connect(addr)?;
+                Ok(Connection::Udp(socket))
+            }
+        }
+    }
+
+    /// Envoie le début de `data`, renvoie le nombre d'octets partis.
+    fn send(&mut self, data: &str) -> io::Result<usize> {
+        match self {
+            Connection::Tcp(stream) => stream.write(data.as_bytes()),
+            Connection::Udp(socket) => socket.send(data.as_bytes()).map(|_| data.len()),
+        }
+    }
+}
+
+struct NetworkState {
+    /// Lignes pas encore envoyées, terminées par `\n`.
+    pending: VecDeque<String>,
+    dropped: u64,
+    connected: bool,
+    /// Le thread d'envoi a une ligne en cours.
+    busy: bool,
+    /// Nombre de connexions échouées, pour que `flush` n'attende pas un collecteur absent.
+    failed_attempts: u64,
+    closed: bool,
+}
+
+struct Shared {
+    state: Mutex<NetworkState>,
+    /// Nouvelles lignes ou fermeture du sink.
+    wake: Condvar,
+    /// Tampon vidé ou connexion échouée.
+    progress: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, NetworkState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Envoie les logs à un collecteur (Vector, Fluent Bit...) en TCP ou UDP, au format JSON Lines
+/// par défaut.
+///
+/// Les lignes sont envoyées par un thread dédié : un log ne fait que les ajouter à un tampon
+/// borné (les plus anciennes sont abandonnées quand il est plein). Pendant une coupure, la
+/// connexion est retentée avec un délai qui double à chaque échec.
+///
+/// # Exemple
+/// ```no_run
+/// # use macros_libs::logging::*;
+/// add_sink(NetworkSink::tcp("127.0.0.1:9000").with_buffer(50_000));
+/// ```
+pub struct NetworkSink {
+    addr: String,
+    protocol: Protocol,
+    capacity: usize,
+    min_delay: Duration,
+    max_delay: Duration,
+    shared: Arc<Shared>,
+    sender: Once,
+}
+
+impl NetworkSink {
+    /// Connexion TCP vers `addr` (`"host:port"`), établie au premier log.
+    pub fn tcp(addr: &str) -> Self {
+        Self::new(addr, Protocol::Tcp)
+    }
+
+    /// Datagrammes UDP vers `addr` (`"host:port"`).
+    pub fn udp(addr: &str) -> Self {
+        Self::new(addr, Protocol::Udp)
+    }
+
+    pub fn new(addr: &str, protocol: Protocol) -> Self {
+        Self {
+            addr: addr.to_string(),
+            protocol,
+            capacity: DEFAULT_NETWORK_BUFFER,
+            min_delay: DEFAULT_RECONNECT_DELAY,
+            max_delay: DEFAULT_MAX_RECONNECT_DELAY,
+            shared: Arc::new(Shared {
+                state: Mutex::new(NetworkState {
+                    pending: VecDeque::new(),
+                    dropped: 0,
+                    connected: false,
+                    busy: false,
+                    failed_attempts: 0,
+                    closed: false,
+                }),
+                wake: Condvar::new(),
+                progress: Condvar::new(),
+            }),
+            sender: Once::new(),
+        }
+    }
+
+    /// Nombre maximal de lignes gardées pendant une coupure.
+    pub fn with_buffer(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Délais de reconnexion : `min` après la première erreur, doublé jusqu'à `max`.
+    pub fn with_backoff(mut self, min: Duration, max: Duration) -> Self {
+        self.min_delay = min;
+        self.max_delay = max.max(min);
+        self
+    }
+
+    /// Nombre de lignes abandonnées faute de place dans le tampon.
+    pub fn dropped(&self) -> u64 {
+        self.shared.lock().dropped
+    }
+
+    /// `true` si une connexion est ouverte.
+    pub fn is_connected(&self) -> bool {
+        self.shared.lock().connected
+    }
+
+    /// Lance le thread d'envoi au premier log, avec les réglages définitifs.
+    fn start_sender(&self) {
+        self.sender.call_once(|| {
+            let sender = Sender {
+                addr: self.addr.clone(),
+                protocol: self.protocol,
+                min_delay: self.min_delay,
+                max_delay: self.max_delay,
+                shared: Arc::clone(&self.shared),
+            };
+            thread::Builder::new()
+                .name("macros_libs-net".to_string())
+                .spawn(move || sender.run())
+                .expect("impossible de lancer le thread d'envoi réseau");
+        });
+    }
+}
+
+impl Drop for NetworkSink {
+    /// Le thread d'envoi termine le tampon s'il est connecté, puis s'arrête.
+    fn drop(&mut self) {
+        self.shared.lock().closed = true;
+        self.shared.wake.notify_all();
+    }
+}
+
+struct Sender {
+    addr: String,
+    protocol: Protocol,
+    min_delay: Duration,
+    max_delay: Duration,
+    shared: Arc<Shared>,
+}
+
+impl Sender {
+    fn run(self) {
+        let mut connection = None;
+        let mut delay = self.min_delay;
+        while let Some(line) = self.next_line() {
+            // Octets de `line` déjà envoyés sur la connexion courante : une écriture TCP
+            // interrompue reprend là où elle s'est arrêtée.
+            let mut sent = 0;
+            while sent < line.len() {
+                let Some(open) = &mut connection else {
+                    match self.connect() {
+                        Ok(opened) => {
+                            connection = Some(opened);
+                            delay = self.min_delay;
+                            self.shared.lock().connected = true;
+                        }
+                        Err(_) => {
+                            if !self.wait_backoff(delay) {
+                                return;
+                            }
+                            delay = (delay * 2).min(self.max_delay);
+                        }
+                    }
+                    continue;
+                };
+                match open.send(&line[sent..]) {
+                    Ok(0) => {}
+                    Ok(n) => {
+                        sent += n;
+                        continue;
+                    }
+                    Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {
+                        // Collecteur lent : la connexion est gardée, sauf si le sink est détruit.
+                        if self.shared.lock().closed {
+                            return;
+                        }
+                        continue;
+                    }
+                    Err(_) => {}
+                }
+                // Connexion perdue : la ligne entière est renvoyée sur la suivante.
+                connection = None;
+                sent = 0;
+                self.shared.lock().connected = false;
+            }
+        }
+    }
+
+    /// Attend la prochaine ligne, `None` une fois le sink détruit et le tampon vide.
+    fn next_line(&self) -> Option<String> {
+        let mut state = self.shared.lock();
+        state.busy = false;
+        loop {
+            if let Some(line) = state.pending.pop_front() {
+                state.busy = true;
+                return Some(line);
+            }
+            self.shared.progress.notify_all();
+            if state.closed {
+                return None;
+            }
+            state = self.shared.wake.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Note l'échec puis attend `delay`. Renvoie `false` si le sink a été détruit entre-temps.
+    fn wait_backoff(&self, delay: Duration) -> bool {
+        let mut state = self.shared.lock();
+        state.connected = false;
+        state.failed_attempts += 1;
+        self.shared.progress.notify_all();
+        let (state, _) = self
+            .shared
+            .wake
+            .wait_timeout_while(state, delay, |state| !state.closed)
+            .unwrap_or_else(|e| e.into_inner());
+        !state.closed
+    }
+
+    fn connect(&self) -> io::Result<Connection> {
+        let mut last_error = None;
+        for addr in self.addr.to_socket_addrs()? {
+            match Connection::open(self.protocol, addr) {
+                Ok(connection) => return Ok(connection),
+                Err(err) => last_error = Some(err),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "adresse introuvable")))
+    }
+}
+
+impl LogSink for NetworkSink {
+    fn write(&self, _record: &LogRecord, line: &str) -> io::Result<()> {
+        self.start_sender();
+        let mut state = self.shared.lock();
+        if state.pending.len() >= self.capacity {
+            state.pending.pop_front();
+            state.dropped += 1;
+        }
+        state.pending.push_back(format!("{}\n", line));
+        self.shared.wake.notify_one();
+        Ok(())
+    }
+
+    /// Attend que le tampon soit envoyé, au plus `FLUSH_TIMEOUT`. Échoue si la prochaine
+    /// tentative de connexion échoue ou si des lignes restent en attente.
+    fn flush(&self) -> io::Result<()> {
+        let state = self.shared.lock();
+        if state.pending.is_empty() && !state.busy {
+            return Ok(());
+        }
+        let attempts = state.failed_attempts;
+        let (state, _) = self
+            .shared
+            .progress
+            .wait_timeout_while(state, FLUSH_TIMEOUT, |state| {
+                (state.busy || !state.pending.is_empty()) && state.failed_attempts == attempts && !state.closed
+            })
+            .unwrap_or_else(|e| e.into_inner());
+        if state.pending.is_empty() && !state.busy {
+            Ok(())
+        } else {
+            Err(io::Error::new(io::ErrorKind::TimedOut, "lignes de log pas encore envoyées au collecteur"))
+        }
+    }
+
+    fn default_format(&self) -> LogFormat {
+        LogFormat::JsonLines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    use super::*;
+    use crate::logging::{CallSite, Level};
+
+    #[test]
+    fn tcp_buffers_while_collector_is_down() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let delay = Duration::from_millis(20);
+        let sink = NetworkSink::tcp(&addr.to_string()).with_buffer(3).with_backoff(delay, delay);
+        let site = CallSite { module: module_path!(), file: file!(), line: line!() };
+        let record = LogRecord::new(Level::Info, String::new(), Vec::new(), site);
+        sink.write(&record, "ligne 0").unwrap();
+        // Le thread d'envoi garde `ligne 0` en cours et attend avant de se reconnecter.
+        assert!(sink.flush().is_err());
+        for i in 1..5 {
+            sink.write(&record, &format!("ligne {}", i)).unwrap();
+        }
+        assert!(!sink.is_connected());
+        assert_eq!(sink.dropped(), 1);
+
+        let listener = TcpListener::bind(addr).unwrap();
+        sink.flush().unwrap();
+        assert!(sink.is_connected());
+        drop(sink);
+
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut received = String::new();
+        stream.read_to_string(&mut received).unwrap();
+        assert_eq!(received, "ligne 0\nligne 2\nligne 3\nligne 4\n");
+    }
+
+    #[test]
+    fn write_does_not_wait_for_the_network() {
+        // Adresse non routable : une connexion resterait bloquée jusqu'au délai d'attente.
+        let sink = NetworkSink::tcp("10.255.255.1:9").with_buffer(10);
+        let site = CallSite { module: module_path!(), file: file!(), line: line!() };
+        let record = LogRecord::new(Level::Info, String::new(), Vec::new(), site);
+        let start = std::time::Instant::now();
+        for i in 0..20 {
+            sink.write(&record, &format!("ligne {}", i)).unwrap();
+        }
+        assert!(start.elapsed() < Duration::from_millis(500));
+        // Le thread d'envoi a pu prendre la première ligne avant que le tampon déborde.
+        assert!(sink.dropped() >= 9);
+    }
+}
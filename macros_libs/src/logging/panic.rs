@@ -0,0 +1,70 @@
+use std::backtrace::Backtrace;
+use std::panic::{self, PanicHookInfo};
+use std::sync::Once;
+
+use super::{CallSite, Field, Level, LogRecord, emit_record, flush_logs, redact};
+
+static INSTALL: Once = Once::new();
+
+/// Options de `install_panic_logger_with`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanicLoggerOptions {
+    /// Niveau du log écrit pour chaque panique (`Level::Error` par défaut).
+    pub level: Level,
+    /// Ajoute la pile d'appels au message, même sans `RUST_BACKTRACE`.
+    pub backtrace: bool,
+}
+
+impl Default for PanicLoggerOptions {
+    fn default() -> Self {
+        Self { level: Level::Error, backtrace: false }
+    }
+}
+
+/// Écrit chaque panique dans les logs (message, emplacement, thread), vide tous les sinks
+/// puis appelle le hook précédent, qui affiche toujours la panique sur stderr.
+///
+/// Le log n'est ni filtré par niveau ni intercepté par `capture_logs`. Seul le premier
+/// appel (ou celui de `install_panic_logger_with`) installe le hook.
+pub fn install_panic_logger() {
+    install_panic_logger_with(PanicLoggerOptions::default());
+}
+
+/// Comme `install_panic_logger`, avec un niveau (par exemple `Level::Fatal`) et la pile d'appels.
+///
+/// # Exemple
+/// ```
+/// # use macros_libs::logging::*;
+/// install_panic_logger_with(PanicLoggerOptions { level: Level::Fatal, backtrace: true });
+/// ```
+pub fn install_panic_logger_with(options: PanicLoggerOptions) {
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            log_panic(info, options);
+            previous(info);
+        }));
+    });
+}
+
+fn log_panic(info: &PanicHookInfo<'_>, options: PanicLoggerOptions) {
+    let payload = info.payload();
+    let reason = match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(reason), _) => *reason,
+        (None, Some(reason)) => reason.as_str(),
+        (None, None) => "Box<dyn Any>",
+    };
+    let mut fields = Vec::new();
+    if let Some(location) = info.location() {
+        fields.push(Field::new("location", location.to_string()));
+    }
+    let site = CallSite { module: module_path!(), file: file!(), line: line!() };
+    let mut record = LogRecord::new(options.level, String::new(), fields, site);
+    // Nom du thread, ou `thread-<id>` s'il n'en a pas.
+    record.message = format!("panique dans le thread '{}' : {}", record.thread_display(), reason);
+    if options.backtrace {
+        record.message.push_str(&format!("\n{}", Backtrace::force_capture()));
+    }
+    emit_record(redact(record));
+    flush_logs();
+}
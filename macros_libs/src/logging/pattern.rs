@@ -0,0 +1,228 @@
+use std::error::Error;
+use std::fmt::{self, Write};
+
+use chrono::format::{Item, StrftimeItems};
+
+use super::LogRecord;
+
+/// Format de date utilisé par `{time}` sans précision.
+const DEFAULT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Modèle de ligne de log, analysé une seule fois.
+///
+/// Marqueurs disponibles : `{time}` ou `{time:%H:%M:%S%.3f}`, `{level}`, `{msg}`, `{module}`,
+/// `{file}`, `{line}`, `{thread}`, `{thread_id}` et `{fields}`. Tous sauf `{time}` acceptent un alignement
+/// comme `{level:>7}`, `{module:<20}` ou `{level:^9}`. `{{` et `}}` produisent des accolades.
+/// Sans `{fields}`, les champs non vides sont ajoutés en fin de ligne (`clé=valeur`).
+///
+/// # Exemple
+/// ```
+/// # use macros_libs::logging::*;
+/// # fn main() -> Result<(), PatternError> {
+/// let pattern = LogPattern::parse("{time:%H:%M:%S%.3f} {level:>7} {thread} {module}:{line} - {msg}")?;
+/// set_sink_format(CONSOLE_SINK, LogFormat::Pattern(pattern.into()));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogPattern {
+    parts: Vec<Part>,
+    /// Le modèle contient `{fields}`.
+    has_fields: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Time(String),
+    Value(Placeholder, Option<Align>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Level,
+    Message,
+    Module,
+    File,
+    Line,
+    Thread,
+    ThreadId,
+    Fields,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left(usize),
+    Right(usize),
+    Center(usize),
+}
+
+/// Modèle de ligne invalide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+    pub pattern: String,
+    pub reason: String,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "modèle de log invalide {:?} : {}", self.pattern, self.reason)
+    }
+}
+
+impl Error for PatternError {}
+
+impl LogPattern {
+    pub fn parse(pattern: &str) -> Result<Self, PatternError> {
+        let error = |reason: String| PatternError { pattern: pattern.to_string(), reason };
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut spec = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => spec.push(c),
+                            None => return Err(error("accolade non fermée".to_string())),
+                        }
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(parse_placeholder(&spec).map_err(error)?);
+                }
+                '}' => return Err(error("accolade fermante sans ouverture".to_string())),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        let has_fields = parts.iter().any(|part| matches!(part, Part::Value(Placeholder::Fields, _)));
+        Ok(Self { parts, has_fields })
+    }
+
+    /// Applique le modèle à un enregistrement.
+    pub fn format(&self, record: &LogRecord) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => out.push_str(text),
+                Part::Time(format) => {
+                    let _ = write!(out, "{}", record.time.format(format));
+                }
+                Part::Value(placeholder, align) => {
+                    let value = placeholder_value(*placeholder, record);
+                    let _ = match align {
+                        None => write!(out, "{}", value),
+                        Some(Align::Left(width)) => write!(out, "{:<width$}", value, width = width),
+                        Some(Align::Right(width)) => write!(out, "{:>width$}", value, width = width),
+                        Some(Align::Center(width)) => write!(out, "{:^width$}", value, width = width),
+                    };
+                }
+            }
+        }
+        if !self.has_fields && !record.fields.is_empty() {
+            out.push(' ');
+            out.push_str(&placeholder_value(Placeholder::Fields, record));
+        }
+        out
+    }
+}
+
+impl std::str::FromStr for LogPattern {
+    type Err = PatternError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        Self::parse(pattern)
+    }
+}
+
+fn parse_placeholder(spec: &str) -> Result<Part, String> {
+    let (name, arg) = match spec.split_once(':') {
+        Some((name, arg)) => (name.trim(), Some(arg)),
+        None => (spec.trim(), None),
+    };
+    if name == "time" {
+        let format = arg.unwrap_or(DEFAULT_TIME_FORMAT);
+        if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+            return Err(format!("format de date invalide {:?}", format));
+        }
+        return Ok(Part::Time(format.to_string()));
+    }
+    let placeholder = match name {
+        "level" => Placeholder::Level,
+        "msg" | "message" => Placeholder::Message,
+        "module" => Placeholder::Module,
+        "file" => Placeholder::File,
+        "line" => Placeholder::Line,
+        "thread" => Placeholder::Thread,
+        "thread_id" => Placeholder::ThreadId,
+        "fields" => Placeholder::Fields,
+        other => return Err(format!("marqueur inconnu {{{}}}", other)),
+    };
+    let align = match arg.map(str::trim) {
+        None | Some("") => None,
+        Some(arg) => Some(parse_align(arg).ok_or_else(|| format!("alignement invalide {:?}", arg))?),
+    };
+    Ok(Part::Value(placeholder, align))
+}
+
+fn parse_align(arg: &str) -> Option<Align> {
+    let (kind, width): (fn(usize) -> Align, &str) = match arg.chars().next()? {
+        '<' => (Align::Left, &arg[1..]),
+        '>' => (Align::Right, &arg[1..]),
+        '^' => (Align::Center, &arg[1..]),
+        _ => (Align::Left, arg),
+    };
+    width.parse().ok().map(kind)
+}
+
+fn placeholder_value(placeholder: Placeholder, record: &LogRecord) -> String {
+    match placeholder {
+        Placeholder::Level => record.level.to_string(),
+        Placeholder::Message => record.message.clone(),
+        Placeholder::Module => record.module.to_string(),
+        Placeholder::File => record.file.to_string(),
+        Placeholder::Line => record.line.to_string(),
+        Placeholder::Thread => record.thread_display(),
+        Placeholder::ThreadId => record.thread_id.to_string(),
+        Placeholder::Fields => record.fields.iter().map(|f| f.to_string()).collect::<Vec<_>>().join(" "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::{CallSite, Field, Level};
+
+    fn record() -> LogRecord {
+        let site = CallSite { module: "app::db", file: "src/db.rs", line: 12 };
+        LogRecord::new(Level::Info, "connecté".to_string(), vec![Field::new("user", "bob"), Field::new("id", 7)], site)
+    }
+
+    #[test]
+    fn fields_are_appended_without_placeholder() {
+        let pattern = LogPattern::parse("{level} - {msg}").unwrap();
+        assert_eq!(pattern.format(&record()), "Info - connecté user=bob id=7");
+    }
+
+    #[test]
+    fn fields_placeholder_is_used_as_is() {
+        let pattern = LogPattern::parse("[{fields}] {msg}").unwrap();
+        assert_eq!(pattern.format(&record()), "[user=bob id=7] connecté");
+        let empty = LogRecord { fields: Vec::new(), ..record() };
+        assert_eq!(LogPattern::parse("{msg}").unwrap().format(&empty), "connecté");
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Compteur d'un appel `log_every_n!`.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct EveryN(AtomicU64);
+
+impl EveryN {
+    pub const fn new() -> Self {
+        Self(AtomicU64::new(0))
+    }
+
+    /// `true` au premier appel puis tous les `n` appels.
+    pub fn tick(&self, n: u64) -> bool {
+        self.0.fetch_add(1, Ordering::Relaxed).is_multiple_of(n.max(1))
+    }
+}
+
+/// Dernier passage d'un appel `log_throttled!`.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct Throttle(Mutex<Option<Instant>>);
+
+impl Throttle {
+    pub const fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    /// `true` si au moins `interval` s'est écoulé depuis le dernier `true`.
+    pub fn ready(&self, interval: Duration) -> bool {
+        let mut last = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        if last.is_some_and(|last| now.duration_since(last) < interval) {
+            return false;
+        }
+        *last = Some(now);
+        true
+    }
+}
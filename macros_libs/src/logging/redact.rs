@@ -0,0 +1,117 @@
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+use super::{Field, FieldValue, LogRecord};
+
+/// Texte qui remplace les secrets.
+pub const REDACTED: &str = "***";
+
+/// Noms de champs masqués par `Redaction::with_defaults` (comparaison sans casse, sur une partie
+/// du nom : `access_token` est masqué par `token`).
+pub const DEFAULT_REDACTED_FIELDS: &[&str] =
+    &["password", "passwd", "secret", "token", "authorization", "api_key", "apikey", "cookie"];
+
+/// Secrets courants : en-têtes `Authorization`, `password=...`, jetons Bearer, clés AWS.
+/// Le groupe `secret` désigne la partie masquée, sinon tout le texte reconnu l'est.
+static DEFAULT_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        r#"(?i)\b(?:proxy-)?authorization\s*[:=]\s*(?P<secret>(?:(?:bearer|basic|digest|token)\s+)?[^\s,;"']+)"#,
+        r#"(?i)\bbearer\s+(?P<secret>[A-Za-z0-9\-._~+/]+=*)"#,
+        r#"(?i)\b(?:password|passwd|pwd|secret|api[_-]?key|access[_-]?token|refresh[_-]?token|token)\s*[=:]\s*(?P<secret>[^\s&,;"']+)"#,
+        r"(?i)\baws_secret_access_key\s*[=:]\s*(?P<secret>[A-Za-z0-9/+=]{40})",
+        r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b",
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).expect("motif de masquage intégré invalide"))
+    .collect()
+});
+
+/// Règles de masquage appliquées à chaque log avant les sinks (et avant `capture_logs`).
+///
+/// # Exemple
+/// ```
+/// # use macros_libs::logging::*;
+/// # fn main() -> Result<(), regex::Error> {
+/// let redaction = Redaction::with_defaults().pattern(r"\bcard=(?P<secret>\d{12})")?.field("iban");
+/// set_log_redaction(redaction);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Redaction {
+    patterns: Vec<Regex>,
+    fields: Vec<String>,
+}
+
+impl Redaction {
+    /// Aucune règle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Motifs intégrés et `DEFAULT_REDACTED_FIELDS`.
+    pub fn with_defaults() -> Self {
+        Self {
+            patterns: DEFAULT_PATTERNS.clone(),
+            fields: DEFAULT_REDACTED_FIELDS.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    /// Ajoute une expression régulière. Si elle a un groupe nommé `secret`, seul ce groupe
+    /// est masqué, sinon tout le texte reconnu.
+    pub fn pattern(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.patterns.push(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Masque la valeur des champs dont le nom contient `name`, sans tenir compte de la casse.
+    pub fn field(mut self, name: &str) -> Self {
+        self.fields.push(name.to_ascii_lowercase());
+        self
+    }
+
+    /// Masque les secrets du message et des champs de `record`.
+    pub fn apply(&self, record: &mut LogRecord) {
+        if let Cow::Owned(message) = self.redact_text(&record.message) {
+            record.message = message;
+        }
+        for field in &mut record.fields {
+            self.redact_field(field);
+        }
+    }
+
+    fn redact_field(&self, field: &mut Field) {
+        let key = field.key.to_ascii_lowercase();
+        if self.fields.iter().any(|name| key.contains(name.as_str())) {
+            field.value = FieldValue::Str(REDACTED.to_string());
+        } else if let FieldValue::Str(value) = &field.value
+            && let Cow::Owned(redacted) = self.redact_text(value)
+        {
+            field.value = FieldValue::Str(redacted);
+        }
+    }
+
+    fn redact_text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        for pattern in &self.patterns {
+            if let Cow::Owned(redacted) = pattern.replace_all(&text, replace_secret) {
+                text = Cow::Owned(redacted);
+            }
+        }
+        text
+    }
+}
+
+fn replace_secret(caps: &Captures) -> String {
+    let whole = caps.get(0).expect("le groupe 0 existe toujours");
+    match caps.name("secret") {
+        Some(secret) => {
+            let before = &whole.as_str()[..secret.start() - whole.start()];
+            let after = &whole.as_str()[secret.end() - whole.start()..];
+            format!("{}{}{}", before, REDACTED, after)
+        }
+        None => REDACTED.to_string(),
+    }
+}
@@ -0,0 +1,259 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+/// Nombre de sauvegardes conservées par défaut lors d'une rotation par taille.
+pub const DEFAULT_MAX_LOG_BACKUPS: usize = 5;
+
+/// Chemin de la `index`-ième sauvegarde de `path` (`app.log` -> `app.log.1`).
+pub(crate) fn backup_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Indique si écrire `incoming` octets dans un fichier de `current` octets dépasse `max`.
+pub(crate) fn needs_size_rotation(current: u64, incoming: u64, max: u64) -> bool {
+    current > 0 && current + incoming > max
+}
+
+/// Décale les sauvegardes (`app.log.1` -> `app.log.2`...) puis renomme `path` en `app.log.1`.
+///
+/// La plus ancienne sauvegarde au-delà de `backups` est supprimée. Avec `backups == 0`,
+/// le fichier courant est simplement supprimé. Les sauvegardes déjà compressées
+/// (`app.log.1.gz`) sont décalées de la même façon.
+pub(crate) fn rotate_by_size(path: &Path, backups: usize) -> io::Result<()> {
+    if backups == 0 {
+        return remove_if_exists(path);
+    }
+    // Les sauvegardes en attente de compression suivent leurs renommages.
+    let mut jobs = pending_compressions();
+    for suffix in ["", ".gz"] {
+        let oldest = with_suffix(&backup_path(path, backups), suffix);
+        remove_if_exists(&oldest)?;
+        jobs.removed(&oldest);
+        for index in (1..backups).rev() {
+            let from = with_suffix(&backup_path(path, index), suffix);
+            if from.exists() {
+                let to = with_suffix(&backup_path(path, index + 1), suffix);
+                fs::rename(&from, &to)?;
+                jobs.renamed(&from, &to);
+            }
+        }
+    }
+    fs::rename(path, backup_path(path, 1))
+}
+
+#[cfg(feature = "compression")]
+use super::compress::jobs as pending_compressions;
+
+#[cfg(not(feature = "compression"))]
+fn pending_compressions() -> NoCompression {
+    NoCompression
+}
+
+/// Sans la feature `compression`, aucune sauvegarde n'attend d'être compressée.
+#[cfg(not(feature = "compression"))]
+struct NoCompression;
+
+#[cfg(not(feature = "compression"))]
+impl NoCompression {
+    fn renamed(&mut self, _from: &Path, _to: &Path) {}
+
+    fn removed(&mut self, _path: &Path) {}
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Rotation des fichiers de log selon le temps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    /// Un seul fichier, seule la rotation par taille s'applique.
+    #[default]
+    Never,
+    /// Un fichier par heure : `app-2026-10-18-14.log`.
+    Hourly,
+    /// Un fichier par jour : `app-2026-10-18.log`.
+    Daily,
+}
+
+/// Horloge utilisée pour dater les fichiers et décider du changement de période.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RotationClock {
+    #[default]
+    Local,
+    Utc,
+}
+
+impl RotationClock {
+    pub(crate) fn now(&self) -> NaiveDateTime {
+        match self {
+            RotationClock::Local => chrono::Local::now().naive_local(),
+            RotationClock::Utc => chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl Rotation {
+    fn pattern(&self) -> Option<&'static str> {
+        match self {
+            Rotation::Never => None,
+            Rotation::Hourly => Some("%Y-%m-%d-%H"),
+            Rotation::Daily => Some("%Y-%m-%d"),
+        }
+    }
+
+    /// Identifiant de la période contenant `now`, `None` sans rotation temporelle.
+    pub(crate) fn period(&self, now: NaiveDateTime) -> Option<String> {
+        self.pattern().map(|p| now.format(p).to_string())
+    }
+
+    /// Durée d'une période.
+    fn length(&self) -> chrono::Duration {
+        match self {
+            Rotation::Never => chrono::Duration::zero(),
+            Rotation::Hourly => chrono::Duration::hours(1),
+            Rotation::Daily => chrono::Duration::days(1),
+        }
+    }
+
+    fn parse_period(&self, period: &str) -> Option<NaiveDateTime> {
+        match self {
+            Rotation::Never => None,
+            Rotation::Hourly => {
+                NaiveDateTime::parse_from_str(&format!("{}:00", period), "%Y-%m-%d-%H:%M").ok()
+            }
+            Rotation::Daily => NaiveDate::parse_from_str(period, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0)),
+        }
+    }
+}
+
+fn split_name(base: &Path) -> (String, Option<String>) {
+    let stem = base.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let ext = base.extension().map(|e| e.to_string_lossy().into_owned());
+    (stem, ext)
+}
+
+/// Chemin du fichier actif pour `period` (`app.log` -> `app-2026-10-18.log`).
+pub(crate) fn dated_path(base: &Path, period: &str) -> PathBuf {
+    let (stem, ext) = split_name(base);
+    let name = match ext {
+        Some(ext) => format!("{}-{}.{}", stem, period, ext),
+        None => format!("{}-{}", stem, period),
+    };
+    base.with_file_name(name)
+}
+
+/// `true` si `name` est exactement le fichier daté de `period`, éventuellement suivi de `.N`
+/// et/ou `.gz` (`app-2026-10-18.log`, `app-2026-10-18.log.2.gz`).
+fn is_dated_log(name: &str, base: &Path, period: &str) -> bool {
+    let dated = dated_path(base, period);
+    let Some(dated) = dated.file_name().map(|n| n.to_string_lossy()) else {
+        return false;
+    };
+    let Some(rest) = name.strip_prefix(dated.as_ref()) else {
+        return false;
+    };
+    let rest = rest.strip_suffix(".gz").unwrap_or(rest);
+    match rest.strip_prefix('.') {
+        Some(index) => !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()),
+        None => rest.is_empty(),
+    }
+}
+
+/// Supprime les fichiers datés (et leurs sauvegardes) dont la période s'est terminée avant
+/// `now - retention` : un fichier reçoit des lignes jusqu'à la fin de sa période.
+pub(crate) fn remove_expired(
+    base: &Path,
+    rotation: Rotation,
+    now: NaiveDateTime,
+    retention: Duration,
+) -> io::Result<()> {
+    let Some(pattern) = rotation.pattern() else {
+        return Ok(());
+    };
+    let Ok(retention) = chrono::Duration::from_std(retention) else {
+        return Ok(());
+    };
+    let limit = now - retention;
+    let (stem, _) = split_name(base);
+    let prefix = format!("{}-", stem);
+    let period_len = now.format(pattern).to_string().len();
+    let dir = match base.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let Some(period) = name.strip_prefix(&prefix).and_then(|rest| rest.get(..period_len)) else {
+            continue;
+        };
+        if let Some(start) = rotation.parse_period(period)
+            && start + rotation.length() <= limit
+            && is_dated_log(&name, base, period)
+        {
+            remove_if_exists(&entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retention_counts_from_the_end_of_the_period() {
+        let dir = std::env::temp_dir().join(format!("macros_libs-retention-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let base = dir.join("app.log");
+        let names = ["app-2026-10-15.log", "app-2026-10-15.log.1.gz", "app-2026-10-16.log", "app-2026-10-16.log.1"];
+        for name in names {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        // Limite : 2026-10-16 09:58. Le 16 a reçu des lignes jusqu'à 23:59, il est gardé.
+        let now = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_opt(9, 58, 0).unwrap();
+        remove_expired(&base, Rotation::Daily, now, Duration::from_secs(2 * 24 * 3600)).unwrap();
+
+        let exists: Vec<bool> = names.iter().map(|name| dir.join(name).exists()).collect();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(exists, [false, false, true, true]);
+    }
+
+    #[test]
+    fn hourly_retention_keeps_the_previous_hour() {
+        let dir = std::env::temp_dir().join(format!("macros_libs-retention-hourly-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let base = dir.join("app.log");
+        let names = ["app-2026-10-18-07.log", "app-2026-10-18-08.log"];
+        for name in names {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        let now = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_opt(9, 30, 0).unwrap();
+        remove_expired(&base, Rotation::Hourly, now, Duration::from_secs(3600)).unwrap();
+
+        let exists: Vec<bool> = names.iter().map(|name| dir.join(name).exists()).collect();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(exists, [false, true]);
+    }
+}
@@ -0,0 +1,368 @@
+use std::io::{self, Write};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::file::FileWriter;
+use crate::color::{Stream, colors_enabled_for};
+
+use super::{
+    FileOptions, Level, LogFormat, LogRecord, RecordMetadata, config, console_stream, format_record_with,
+};
+
+/// Destination des logs : console, fichier, mémoire ou implémentation personnalisée.
+pub trait LogSink: Send + Sync {
+    /// Écrit un enregistrement ; `line` est déjà mise en forme selon le format du sink.
+    fn write(&self, record: &LogRecord, line: &str) -> io::Result<()>;
+
+    /// Vide les éventuels tampons.
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// `true` si les lignes lisibles (`Plain`, `Pattern`) de ce niveau doivent recevoir sa couleur.
+    fn colored(&self, _level: &Level) -> bool {
+        false
+    }
+
+    /// Format appliqué par `add_sink`, modifiable ensuite avec `set_sink_format`.
+    fn default_format(&self) -> LogFormat {
+        LogFormat::Plain
+    }
+}
+
+/// Identifiant d'un sink enregistré.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SinkId(usize);
+
+/// Sink console enregistré par défaut.
+pub const CONSOLE_SINK: SinkId = SinkId(0);
+/// Sink fichier enregistré par défaut, piloté par `enable_log_saving` et les autres setters.
+pub const FILE_SINK: SinkId = SinkId(1);
+
+struct SinkEntry {
+    id: SinkId,
+    sink: Arc<dyn LogSink>,
+    level: Option<Level>,
+    format: LogFormat,
+    metadata: Option<RecordMetadata>,
+    /// Regroupement des messages répétés, activé par `set_sink_dedup`.
+    repeats: Option<Mutex<Repeats>>,
+}
+
+impl SinkEntry {
+    fn new(id: SinkId, sink: Arc<dyn LogSink>) -> Self {
+        Self { id, sink, level: None, format: LogFormat::Plain, metadata: None, repeats: None }
+    }
+
+    fn metadata(&self) -> RecordMetadata {
+        self.metadata.unwrap_or_else(|| RecordMetadata::default_for(&self.format))
+    }
+
+    /// `true` si le sink doit recevoir des lignes colorées pour `level`.
+    fn colored(&self, level: &Level) -> bool {
+        !self.format.is_structured() && self.sink.colored(level)
+    }
+
+    /// Formate et écrit un enregistrement sans passer par le cache de `dispatch`.
+    fn write_record(&self, record: &LogRecord) {
+        let line = render(record, &self.format, self.metadata(), self.colored(&record.level));
+        let _ = self.sink.write(record, &line);
+    }
+
+    /// Écrit le résumé des répétitions en attente.
+    fn write_repeats(&self) {
+        if let Some(repeats) = &self.repeats
+            && let Some(summary) = lock(repeats).take_summary()
+        {
+            self.write_record(&summary);
+        }
+    }
+}
+
+/// Dernier message écrit par un sink et nombre de répétitions ignorées depuis.
+#[derive(Default)]
+struct Repeats {
+    last: Option<LogRecord>,
+    count: u64,
+}
+
+impl Repeats {
+    /// `true` si `record` répète le dernier message : il est alors compté au lieu d'être écrit.
+    fn is_repeat(&mut self, record: &LogRecord) -> bool {
+        let repeat = self.last.as_ref().is_some_and(|last| {
+            last.level == record.level
+                && last.message == record.message
+                && last.fields == record.fields
+                && last.module == record.module
+                && last.line == record.line
+        });
+        if repeat {
+            self.count += 1;
+        }
+        repeat
+    }
+
+    /// Enregistrement « dernier message répété N fois », s'il y a eu des répétitions.
+    fn take_summary(&mut self) -> Option<LogRecord> {
+        let count = std::mem::take(&mut self.count);
+        let last = self.last.as_ref().filter(|_| count > 0)?;
+        let message = format!("dernier message répété {} fois", count);
+        Some(LogRecord { time: chrono::Local::now(), message, fields: Vec::new(), ..last.clone() })
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+struct SinkRegistry {
+    entries: Vec<SinkEntry>,
+    next_id: usize,
+}
+
+impl SinkRegistry {
+    fn with_defaults() -> Self {
+        let file: Arc<dyn LogSink> = DEFAULT_FILE_SINK.clone();
+        Self {
+            entries: vec![
+                SinkEntry::new(CONSOLE_SINK, Arc::new(ConsoleSink)),
+                SinkEntry::new(FILE_SINK, file),
+            ],
+            next_id: 2,
+        }
+    }
+
+    fn entry_mut(&mut self, id: SinkId) -> Option<&mut SinkEntry> {
+        self.entries.iter_mut().find(|e| e.id == id)
+    }
+}
+
+static SINKS: LazyLock<RwLock<SinkRegistry>> = LazyLock::new(|| RwLock::new(SinkRegistry::with_defaults()));
+pub(crate) static DEFAULT_FILE_SINK: LazyLock<Arc<FileSink>> = LazyLock::new(|| Arc::new(FileSink::global()));
+
+fn sinks() -> RwLockReadGuard<'static, SinkRegistry> {
+    SINKS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn sinks_mut() -> RwLockWriteGuard<'static, SinkRegistry> {
+    SINKS.write().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Ajoute un sink qui reçoit tous les logs, au format `LogSink::default_format`
+/// (`LogFormat::Plain` sauf indication contraire du sink).
+///
+/// # Exemple
+/// ```
+/// # use macros_libs::logging::*;
+/// let errors = add_sink(FileSink::new("errors.log"));
+/// set_sink_level(errors, Level::Error);
+/// set_sink_format(errors, LogFormat::JsonLines);
+/// ```
+pub fn add_sink(sink: impl LogSink + 'static) -> SinkId {
+    let mut registry = sinks_mut();
+    let id = SinkId(registry.next_id);
+    registry.next_id += 1;
+    let format = sink.default_format();
+    registry.entries.push(SinkEntry { format, ..SinkEntry::new(id, Arc::new(sink)) });
+    id
+}
+
+/// Retire un sink (y compris `CONSOLE_SINK` ou `FILE_SINK`) après l'avoir vidé.
+pub fn remove_sink(id: SinkId) -> bool {
+    let mut registry = sinks_mut();
+    let Some(index) = registry.entries.iter().position(|e| e.id == id) else {
+        return false;
+    };
+    let entry = registry.entries.remove(index);
+    entry.write_repeats();
+    let _ = entry.sink.flush();
+    true
+}
+
+/// Niveau minimal propre à un sink, appliqué après le filtre global.
+pub fn set_sink_level(id: SinkId, level: Level) {
+    if let Some(entry) = sinks_mut().entry_mut(id) {
+        entry.level = Some(level);
+    }
+}
+
+pub fn set_sink_format(id: SinkId, format: LogFormat) {
+    if let Some(entry) = sinks_mut().entry_mut(id) {
+        entry.format = format;
+    }
+}
+
+/// Choisit les métadonnées (emplacement, thread) ajoutées par le format du sink.
+pub fn set_sink_metadata(id: SinkId, metadata: RecordMetadata) {
+    if let Some(entry) = sinks_mut().entry_mut(id) {
+        entry.metadata = Some(metadata);
+    }
+}
+
+/// Regroupe les messages identiques consécutifs d'un sink : seul le premier est écrit, puis
+/// « dernier message répété N fois » avant le message suivant ou au prochain vidage.
+pub fn set_sink_dedup(id: SinkId, enabled: bool) {
+    if let Some(entry) = sinks_mut().entry_mut(id) {
+        entry.repeats = enabled.then(|| Mutex::new(Repeats::default()));
+    }
+}
+
+/// Remet uniquement les sinks console et fichier par défaut.
+pub(crate) fn reset_sinks() {
+    let old = std::mem::replace(&mut *sinks_mut(), SinkRegistry::with_defaults());
+    for entry in old.entries {
+        entry.write_repeats();
+        let _ = entry.sink.flush();
+    }
+}
+
+/// Envoie un enregistrement à chaque sink dont le niveau l'accepte.
+pub(crate) fn dispatch(record: &LogRecord) {
+    let registry = sinks();
+    let mut cache: Vec<((&LogFormat, RecordMetadata, bool), String)> = Vec::new();
+    for entry in &registry.entries {
+        if entry.level.is_some_and(|min| record.level < min) {
+            continue;
+        }
+        if let Some(repeats) = &entry.repeats {
+            let mut repeats = lock(repeats);
+            if repeats.is_repeat(record) {
+                continue;
+            }
+            if let Some(summary) = repeats.take_summary() {
+                entry.write_record(&summary);
+            }
+            repeats.last = Some(record.clone());
+        }
+        let key = (&entry.format, entry.metadata(), entry.colored(&record.level));
+        let index = match cache.iter().position(|(k, _)| *k == key) {
+            Some(index) => index,
+            None => {
+                let line = render(record, key.0, key.1, key.2);
+                cache.push((key, line));
+                cache.len() - 1
+            }
+        };
+        let _ = entry.sink.write(record, &cache[index].1);
+    }
+}
+
+fn render(record: &LogRecord, format: &LogFormat, metadata: RecordMetadata, colored: bool) -> String {
+    let line = format_record_with(record, format, metadata);
+    if colored { format!("\x1b[{}m{}\x1b[0m", record.color, line) } else { line }
+}
+
+pub(crate) fn flush_sinks() {
+    for entry in &sinks().entries {
+        entry.write_repeats();
+        let _ = entry.sink.flush();
+    }
+}
+
+/// Affiche les logs sur stdout ou stderr selon le niveau (voir `set_level_stream`),
+/// colorés selon le `ColorMode` sauf en JSON Lines et logfmt.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsoleSink;
+
+impl LogSink for ConsoleSink {
+    fn write(&self, record: &LogRecord, line: &str) -> io::Result<()> {
+        match console_stream(&record.level) {
+            Stream::Stdout => writeln!(io::stdout().lock(), "{}", line),
+            Stream::Stderr => writeln!(io::stderr().lock(), "{}", line),
+        }
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        io::stdout().flush()?;
+        io::stderr().flush()
+    }
+
+    fn colored(&self, level: &Level) -> bool {
+        colors_enabled_for(console_stream(level))
+    }
+}
+
+/// Écrit les logs dans un fichier avec rotation, via un tampon partagé.
+#[derive(Debug)]
+pub struct FileSink {
+    options: Option<FileOptions>,
+    writer: Mutex<FileWriter>,
+}
+
+impl FileSink {
+    /// Fichier `path` avec les options par défaut.
+    pub fn new(path: &str) -> Self {
+        Self::with_options(FileOptions { path: Some(path.to_string()), ..FileOptions::new() })
+    }
+
+    pub fn with_options(options: FileOptions) -> Self {
+        Self { options: Some(options), writer: Mutex::new(FileWriter::new()) }
+    }
+
+    /// Sink qui suit les options fichier de la configuration globale.
+    fn global() -> Self {
+        Self { options: None, writer: Mutex::new(FileWriter::new()) }
+    }
+
+    fn writer(&self) -> MutexGuard<'_, FileWriter> {
+        self.writer.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(crate) fn write_line(&self, line: &str) -> io::Result<()> {
+        match &self.options {
+            Some(options) => self.writer().write_line(options, line),
+            None => self.writer().write_line(&config().file, line),
+        }
+    }
+
+    /// Vide le tampon et ferme le fichier, il sera rouvert au prochain log.
+    pub fn close(&self) {
+        self.writer().close();
+    }
+}
+
+impl LogSink for FileSink {
+    fn write(&self, _record: &LogRecord, line: &str) -> io::Result<()> {
+        self.write_line(line)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.writer().flush()
+    }
+}
+
+/// Garde en mémoire les enregistrements et leurs lignes formatées.
+///
+/// Le sink est partagé : on garde un clone pour relire les logs après `add_sink`.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySink {
+    entries: Arc<Mutex<Vec<(LogRecord, String)>>>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> MutexGuard<'_, Vec<(LogRecord, String)>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn records(&self) -> Vec<LogRecord> {
+        self.entries().iter().map(|(record, _)| record.clone()).collect()
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.entries().iter().map(|(_, line)| line.clone()).collect()
+    }
+
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+}
+
+impl LogSink for MemorySink {
+    fn write(&self, record: &LogRecord, line: &str) -> io::Result<()> {
+        self.entries().push((record.clone(), line.to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+use std::fmt::Write as _;
+use std::io;
+use std::net::{ToSocketAddrs, UdpSocket};
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use super::{Level, LogRecord, LogSink};
+
+/// Socket du démon syslog local.
+#[cfg(unix)]
+pub const SYSLOG_SOCKET: &str = "/dev/log";
+
+/// Identifiant des données structurées RFC 5424 qui portent les champs du log.
+const FIELDS_SD_ID: &str = "fields@32473";
+
+/// Catégorie syslog des messages (RFC 5424, section 6.2.1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Facility {
+    Kern = 0,
+    #[default]
+    User = 1,
+    Mail = 2,
+    Daemon = 3,
+    Auth = 4,
+    Syslog = 5,
+    Lpr = 6,
+    News = 7,
+    Uucp = 8,
+    Cron = 9,
+    AuthPriv = 10,
+    Ftp = 11,
+    Local0 = 16,
+    Local1 = 17,
+    Local2 = 18,
+    Local3 = 19,
+    Local4 = 20,
+    Local5 = 21,
+    Local6 = 22,
+    Local7 = 23,
+}
+
+/// Format des messages envoyés au démon syslog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyslogFormat {
+    /// `<PRI>1 2026-10-18T12:00:00.000000+02:00 host app 1234 - [fields@32473 k="v"] message`
+    #[default]
+    Rfc5424,
+    /// Format BSD historique : `<PRI>Oct 18 12:00:00 host app[1234]: message k=v`
+    Rfc3164,
+}
+
+/// Sévérité syslog (0 = emerg ... 7 = debug) d'un niveau.
+pub fn syslog_severity(level: &Level) -> u8 {
+    match level {
+        Level::Trace | Level::Debug => 7,
+        Level::Info => 6,
+        Level::Success => 5,
+        Level::Warn => 4,
+        Level::Error => 3,
+        Level::Fatal => 2,
+    }
+}
+
+enum Transport {
+    #[cfg(unix)]
+    Unix { socket: Option<UnixDatagram>, path: PathBuf },
+    Udp(UdpSocket),
+}
+
+impl Transport {
+    fn send(&mut self, message: &[u8]) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Transport::Unix { socket, path } => {
+                // Le démon a pu redémarrer : une reconnexion avant d'abandonner le message.
+                if let Some(connected) = socket
+                    && connected.send(message).is_ok()
+                {
+                    return Ok(());
+                }
+                *socket = None;
+                let reconnected = connect_unix(path)?;
+                reconnected.send(message)?;
+                *socket = Some(reconnected);
+                Ok(())
+            }
+            Transport::Udp(socket) => socket.send(message).map(|_| ()),
+        }
+    }
+
+    fn is_local(&self) -> bool {
+        match self {
+            #[cfg(unix)]
+            Transport::Unix { .. } => true,
+            Transport::Udp(_) => false,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn connect_unix(path: &Path) -> io::Result<UnixDatagram> {
+    let socket = UnixDatagram::unbound()?;
+    socket.connect(path)?;
+    Ok(socket)
+}
+
+/// Envoie les logs à un démon syslog (rsyslog, syslog-ng...) par socket Unix ou UDP.
+///
+/// Le niveau devient la sévérité syslog et les champs sont transmis en données structurées
+/// (RFC 5424) ou en `clé=valeur` (RFC 3164). Le `LogFormat` du sink n'est pas utilisé.
+///
+/// # Exemple
+/// ```no_run
+/// # use macros_libs::logging::*;
+/// # fn main() -> std::io::Result<()> {
+/// let sink = SyslogSink::local()?.with_facility(Facility::Local0).with_app_name("billing");
+/// add_sink(sink);
+/// # Ok(())
+/// # }
+/// ```
+pub struct SyslogSink {
+    transport: Mutex<Transport>,
+    facility: Facility,
+    format: SyslogFormat,
+    app_name: String,
+    hostname: String,
+    pid: u32,
+}
+
+impl SyslogSink {
+    /// Socket du démon local, `/dev/log`.
+    #[cfg(unix)]
+    pub fn local() -> io::Result<Self> {
+        Self::unix(SYSLOG_SOCKET)
+    }
+
+    /// Socket Unix datagramme `path`.
+    #[cfg(unix)]
+    pub fn unix(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let socket = connect_unix(&path)?;
+        Ok(Self::with_transport(Transport::Unix { socket: Some(socket), path }))
+    }
+
+    /// Serveur syslog distant en UDP, par exemple `"logs.example.com:514"`.
+    pub fn udp(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let addr = addr
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "adresse syslog introuvable"))?;
+        let bind = if addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+        let socket = UdpSocket::bind(bind)?;
+        socket.connect(addr)?;
+        Ok(Self::with_transport(Transport::Udp(socket)))
+    }
+
+    fn with_transport(transport: Transport) -> Self {
+        Self {
+            transport: Mutex::new(transport),
+            facility: Facility::User,
+            format: SyslogFormat::Rfc5424,
+            app_name: default_app_name(),
+            hostname: hostname(),
+            pid: std::process::id(),
+        }
+    }
+
+    /// Facility des messages (`Facility::User` par défaut).
+    pub fn with_facility(mut self, facility: Facility) -> Self {
+        self.facility = facility;
+        self
+    }
+
+    /// Nom de l'application (nom de l'exécutable par défaut).
+    pub fn with_app_name(mut self, app_name: &str) -> Self {
+        self.app_name = app_name.to_string();
+        self
+    }
+
+    pub fn with_format(mut self, format: SyslogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    fn transport(&self) -> MutexGuard<'_, Transport> {
+        self.transport.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Message syslog complet pour `record`.
+    pub fn format_message(&self, record: &LogRecord) -> String {
+        let pri = self.facility as u8 * 8 + syslog_severity(&record.level);
+        match self.format {
+            SyslogFormat::Rfc5424 => self.format_rfc5424(pri, record),
+            SyslogFormat::Rfc3164 => self.format_rfc3164(pri, record, self.transport().is_local()),
+        }
+    }
+
+    fn format_rfc5424(&self, pri: u8, record: &LogRecord) -> String {
+        let mut out = format!(
+            "<{}>1 {} {} {} {} - ",
+            pri,
+            record.time.format("%Y-%m-%dT%H:%M:%S%.6f%:z"),
+            header_field(&self.hostname, 255),
+            header_field(&self.app_name, 48),
+            self.pid
+        );
+        if record.fields.is_empty() {
+            out.push('-');
+        } else {
+            let _ = write!(out, "[{}", FIELDS_SD_ID);
+            for field in &record.fields {
+                let _ = write!(out, " {}=\"", sd_name(field.key));
+                for c in field.value.to_string().chars() {
+                    if matches!(c, '"' | '\\' | ']') {
+                        out.push('\\');
+                    }
+                    out.push(c);
+                }
+                out.push('"');
+            }
+            out.push(']');
+        }
+        out.push(' ');
+        out.push_str(&record.message);
+        out
+    }
+
+    fn format_rfc3164(&self, pri: u8, record: &LogRecord, local: bool) -> String {
+        let mut out = format!("<{}>{} ", pri, record.time.format("%b %e %H:%M:%S"));
+        // Comme la libc, pas de nom d'hôte vers le démon local : il ajoute le sien.
+        if !local {
+            let _ = write!(out, "{} ", header_field(&self.hostname, 255));
+        }
+        let _ = write!(out, "{}[{}]: {}", header_field(&self.app_name, 32), self.pid, record.message);
+        for field in &record.fields {
+            let _ = write!(out, " {}", field);
+        }
+        out
+    }
+}
+
+impl LogSink for SyslogSink {
+    fn write(&self, record: &LogRecord, _line: &str) -> io::Result<()> {
+        let message = self.format_message(record);
+        self.transport().send(message.as_bytes())
+    }
+}
+
+/// Champ d'en-tête : ASCII imprimable sans espace, tronqué à `max` caractères, `-` si vide.
+fn header_field(value: &str, max: usize) -> String {
+    let value: String = value.chars().filter(|c| c.is_ascii_graphic()).take(max).collect();
+    if value.is_empty() { "-".to_string() } else { value }
+}
+
+/// Nom de paramètre RFC 5424 : ASCII imprimable sans `=`, `]` ni `"`, 32 caractères au plus.
+fn sd_name(key: &str) -> String {
+    let name: String =
+        key.chars().filter(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"')).take(32).collect();
+    if name.is_empty() { "_".to_string() } else { name }
+}
+
+fn default_app_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|path| path.file_stem().map(|name| name.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::UdpSocket;
+    #[cfg(unix)]
+    use std::os::unix::net::UnixDatagram;
+
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::logging::{CallSite, Field};
+
+    fn record() -> LogRecord {
+        let site = CallSite { module: "app::db", file: "src/db.rs", line: 12 };
+        let fields = vec![Field::new("path", "a\"b\\c]d"), Field::new("na=m]e\"", 3)];
+        let mut record = LogRecord::new(Level::Warn, "disque plein".to_string(), fields, site);
+        record.time = chrono::Local.with_ymd_and_hms(2026, 10, 18, 12, 30, 5).unwrap();
+        record
+    }
+
+    fn fixed(sink: SyslogSink) -> SyslogSink {
+        SyslogSink { hostname: "host".to_string(), pid: 42, ..sink.with_facility(Facility::Local0).with_app_name("app") }
+    }
+
+    fn receive_udp(socket: &UdpSocket) -> String {
+        let mut buf = [0; 2048];
+        let len = socket.recv(&mut buf).unwrap();
+        String::from_utf8_lossy(&buf[..len]).into_owned()
+    }
+
+    #[test]
+    fn rfc5424_over_udp() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let sink = fixed(SyslogSink::udp(server.local_addr().unwrap()).unwrap());
+        let record = record();
+        sink.write(&record, "").unwrap();
+
+        // Local0 (16) * 8 + Warn (4) = 132.
+        let expected = format!(
+            "<132>1 {} host app 42 - [fields@32473 path=\"a\\\"b\\\\c\\]d\" name=\"3\"] disque plein",
+            record.time.format("%Y-%m-%dT%H:%M:%S%.6f%:z")
+        );
+        assert_eq!(receive_udp(&server), expected);
+    }
+
+    #[test]
+    fn rfc3164_over_udp_keeps_hostname() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let sink = fixed(SyslogSink::udp(server.local_addr().unwrap()).unwrap()).with_format(SyslogFormat::Rfc3164);
+        sink.write(&record(), "").unwrap();
+
+        assert_eq!(receive_udp(&server), "<132>Oct 18 12:30:05 host app[42]: disque plein path=\"a\\\"b\\\\c]d\" na=m]e\"=3");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rfc3164_over_unix_socket_omits_hostname() {
+        let path = std::env::temp_dir().join(format!("macros_libs-syslog-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let server = UnixDatagram::bind(&path).unwrap();
+        let sink = fixed(SyslogSink::unix(&path).unwrap()).with_format(SyslogFormat::Rfc3164);
+        let mut record = record();
+        record.level = Level::Fatal;
+        record.fields.clear();
+        sink.write(&record, "").unwrap();
+
+        let mut buf = [0; 2048];
+        let len = server.recv(&mut buf).unwrap();
+        let _ = std::fs::remove_file(&path);
+        // Local0 (16) * 8 + Fatal (crit, 2) = 130.
+        assert_eq!(String::from_utf8_lossy(&buf[..len]), "<130>Oct 18 12:30:05 app[42]: disque plein");
+    }
+}
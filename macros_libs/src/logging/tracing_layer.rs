@@ -0,0 +1,140 @@
+use std::fmt;
+
+use tracing_core::field::{Field as TracingField, Visit};
+use tracing_core::span::{Attributes, Id, Record};
+use tracing_core::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use super::{CallSite, Field, FieldValue, Level, log_message, should_log};
+
+/// `Layer` tracing qui affiche les événements avec le format et les sinks de macros_libs.
+///
+/// Les spans englobants apparaissent dans le champ `span` (`requete:db`) et leurs champs
+/// sont ajoutés à ceux de l'événement.
+///
+/// # Exemple
+/// ```
+/// # use macros_libs::logging::MacrosLayer;
+/// use tracing_subscriber::prelude::*;
+/// tracing_subscriber::registry().with(MacrosLayer).init();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MacrosLayer;
+
+/// Champs d'un span, gardés dans ses extensions.
+struct SpanFields(Vec<Field>);
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    fields: Vec<Field>,
+}
+
+impl FieldVisitor {
+    fn push(&mut self, field: &TracingField, value: FieldValue) {
+        self.fields.push(Field { key: field.name(), value });
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_f64(&mut self, field: &TracingField, value: f64) {
+        self.push(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &TracingField, value: i64) {
+        self.push(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &TracingField, value: u64) {
+        self.push(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &TracingField, value: bool) {
+        self.push(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &TracingField, value: &str) {
+        if field.name() == "message" {
+            self.message = Some(value.to_string());
+        } else {
+            self.push(field, value.into());
+        }
+    }
+
+    fn record_debug(&mut self, field: &TracingField, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{:?}", value));
+        } else {
+            self.push(field, FieldValue::Str(format!("{:?}", value)));
+        }
+    }
+}
+
+impl From<&tracing_core::Level> for Level {
+    fn from(level: &tracing_core::Level) -> Self {
+        match *level {
+            tracing_core::Level::ERROR => Level::Error,
+            tracing_core::Level::WARN => Level::Warn,
+            tracing_core::Level::INFO => Level::Info,
+            tracing_core::Level::DEBUG => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl<S> Layer<S> for MacrosLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        span.extensions_mut().insert(SpanFields(visitor.fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = FieldVisitor::default();
+        values.record(&mut visitor);
+        if let Some(fields) = span.extensions_mut().get_mut::<SpanFields>() {
+            fields.0.extend(visitor.fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = Level::from(metadata.level());
+        if !should_log(&level, metadata.module_path().unwrap_or(metadata.target())) {
+            return;
+        }
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let mut fields = Vec::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            let mut names = Vec::new();
+            for span in scope.from_root() {
+                names.push(span.name());
+                if let Some(span_fields) = span.extensions().get::<SpanFields>() {
+                    fields.extend(span_fields.0.iter().cloned());
+                }
+            }
+            fields.insert(0, Field::new("span", names.join(":")));
+        }
+        fields.extend(visitor.fields);
+
+        let site = CallSite {
+            module: metadata.module_path().unwrap_or(metadata.target()),
+            file: metadata.file().unwrap_or("?"),
+            line: metadata.line().unwrap_or(0),
+        };
+        let message = visitor.message.unwrap_or_default();
+        log_message(&level, level.color(), format_args!("{}", message), fields, site);
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, RwLock};
+use std::thread::{self, JoinHandle};
+
+use super::{CallSite, Level, LogRecord, flush_logs, output_record};
+
+static ASYNC_QUEUE: RwLock<Option<Arc<AsyncQueue>>> = RwLock::new(None);
+const WORKER_THREAD_NAME: &str = "macros_libs-log";
+
+/// Comportement quand la file d'attente du thread d'écriture est pleine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Le thread qui logue attend qu'une place se libère.
+    #[default]
+    Block,
+    /// Le nouveau message est abandonné.
+    DropNewest,
+    /// Le plus ancien message en attente est abandonné.
+    DropOldest,
+}
+
+struct QueueState {
+    items: VecDeque<LogRecord>,
+    dropped: u64,
+    /// Le thread d'écriture traite un lot retiré de la file.
+    busy: bool,
+    closed: bool,
+}
+
+struct AsyncQueue {
+    state: Mutex<QueueState>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    idle: Condvar,
+    capacity: usize,
+    policy: OverflowPolicy,
+}
+
+impl AsyncQueue {
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Rend le message si la file est déjà fermée.
+    fn push(&self, record: LogRecord) -> Option<LogRecord> {
+        let mut state = self.lock();
+        // Un log fatal précède la sortie du processus : il n'est jamais abandonné.
+        let policy = if record.level == Level::Fatal { OverflowPolicy::Block } else { self.policy };
+        while state.items.len() >= self.capacity && !state.closed {
+            match policy {
+                OverflowPolicy::Block => {
+                    state = self.not_full.wait(state).unwrap_or_else(|e| e.into_inner());
+                }
+                OverflowPolicy::DropNewest => {
+                    state.dropped += 1;
+                    return None;
+                }
+                OverflowPolicy::DropOldest => {
+                    state.items.pop_front();
+                    state.dropped += 1;
+                }
+            }
+        }
+        if state.closed {
+            return Some(record);
+        }
+        state.items.push_back(record);
+        self.not_empty.notify_one();
+        None
+    }
+
+    /// Attend des messages, renvoie `None` une fois la file fermée et vide.
+    fn pop_all(&self) -> Option<(Vec<LogRecord>, u64)> {
+        let mut state = self.lock();
+        state.busy = false;
+        self.idle.notify_all();
+        while state.items.is_empty() && !state.closed {
+            state = self.not_empty.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+        if state.items.is_empty() && state.closed {
+            return None;
+        }
+        let items = state.items.drain(..).collect();
+        let dropped = std::mem::take(&mut state.dropped);
+        state.busy = true;
+        self.not_full.notify_all();
+        Some((items, dropped))
+    }
+
+    fn close(&self) {
+        let mut state = self.lock();
+        state.closed = true;
+        state.busy = false;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+        self.idle.notify_all();
+    }
+
+    /// Attend que la file soit vide et le dernier lot écrit.
+    fn wait_idle(&self) {
+        let mut state = self.lock();
+        while (state.busy || !state.items.is_empty()) && !state.closed {
+            state = self.idle.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+/// Garde du thread d'écriture asynchrone : à sa destruction, la file est vidée,
+/// le thread arrêté et les fichiers vidés sur disque.
+#[must_use = "le thread d'écriture s'arrête quand la garde est détruite"]
+pub struct AsyncLogGuard {
+    queue: Arc<AsyncQueue>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for AsyncLogGuard {
+    fn drop(&mut self) {
+        {
+            let mut active = ASYNC_QUEUE.write().unwrap_or_else(|e| e.into_inner());
+            if active.as_ref().is_some_and(|q| Arc::ptr_eq(q, &self.queue)) {
+                *active = None;
+            }
+        }
+        self.queue.close();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+        flush_logs();
+    }
+}
+
+/// Écrit les logs depuis un thread dédié, alimenté par une file bornée à `capacity` messages.
+///
+/// # Exemple
+/// ```
+/// # use macros_libs::log_info;
+/// # use macros_libs::logging::*;
+/// let _guard = enable_async_logging(10_000, OverflowPolicy::DropOldest);
+/// log_info!("écrit en arrière-plan");
+/// ```
+pub fn enable_async_logging(capacity: usize, policy: OverflowPolicy) -> AsyncLogGuard {
+    let queue = Arc::new(AsyncQueue {
+        state: Mutex::new(QueueState {
+            items: VecDeque::with_capacity(capacity.min(4096)),
+            dropped: 0,
+            busy: false,
+            closed: false,
+        }),
+        not_empty: Condvar::new(),
+        not_full: Condvar::new(),
+        idle: Condvar::new(),
+        capacity: capacity.max(1),
+        policy,
+    });
+    let worker = Arc::clone(&queue);
+    let handle = thread::Builder::new()
+        .name(WORKER_THREAD_NAME.to_string())
+        .spawn(move || {
+            let worker = CloseOnExit(worker);
+            while let Some((items, dropped)) = worker.0.pop_all() {
+                if dropped > 0 {
+                    output_record_catching(&dropped_record(dropped));
+                }
+                for record in items {
+                    output_record_catching(&record);
+                }
+            }
+        })
+        .expect("impossible de lancer le thread de log");
+    let previous = ASYNC_QUEUE
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .replace(Arc::clone(&queue));
+    if let Some(previous) = previous {
+        previous.close();
+    }
+    AsyncLogGuard {
+        queue,
+        handle: Some(handle),
+    }
+}
+
+/// Ferme la file si le thread d'écriture s'arrête, même sur panique : les messages suivants
+/// sont écrits par l'appelant et `wait_idle` n'attend plus.
+struct CloseOnExit(Arc<AsyncQueue>);
+
+impl Drop for CloseOnExit {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+/// Un sink qui panique ne doit pas arrêter le thread d'écriture : le message est perdu
+/// (la panique est affichée par le hook), les suivants sont écrits.
+fn output_record_catching(record: &LogRecord) {
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| output_record(record)));
+}
+
+/// Envoie le message au thread d'écriture s'il est actif, sinon le rend à l'appelant.
+pub(crate) fn enqueue(record: LogRecord) -> Option<LogRecord> {
+    let active = ASYNC_QUEUE.read().unwrap_or_else(|e| e.into_inner());
+    match active.as_ref() {
+        Some(queue) => {
+            let queue = Arc::clone(queue);
+            drop(active);
+            queue.push(record)
+        }
+        None => Some(record),
+    }
+}
+
+/// Attend que le thread d'écriture ait traité les messages déjà envoyés.
+/// Ne fait rien depuis le thread d'écriture lui-même.
+pub(crate) fn wait_idle() {
+    if thread::current().name() == Some(WORKER_THREAD_NAME) {
+        return;
+    }
+    let active = ASYNC_QUEUE.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(queue) = active {
+        queue.wait_idle();
+    }
+}
+
+fn dropped_record(dropped: u64) -> LogRecord {
+    let site = CallSite { module: module_path!(), file: file!(), line: line!() };
+    LogRecord::new(
+        Level::Warn,
+        format!("{} messages de log perdus (file d'attente pleine)", dropped),
+        Vec::new(),
+        site,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::logging::{LogSink, add_sink, emit_record, remove_sink};
+
+    struct PanicOnBoom(Arc<AtomicUsize>);
+
+    impl LogSink for PanicOnBoom {
+        fn write(&self, record: &LogRecord, _line: &str) -> io::Result<()> {
+            if record.message == "boom" {
+                panic!("sink en panne");
+            }
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn panicking_sink_does_not_stop_the_worker() {
+        let written = Arc::new(AtomicUsize::new(0));
+        let id = add_sink(PanicOnBoom(Arc::clone(&written)));
+        let guard = enable_async_logging(2, OverflowPolicy::Block);
+
+        let (done, finished) = mpsc::channel();
+        thread::spawn(move || {
+            for message in ["avant", "boom", "après 1", "après 2", "après 3"] {
+                let site = CallSite { module: module_path!(), file: file!(), line: line!() };
+                emit_record(LogRecord::new(Level::Info, message.to_string(), Vec::new(), site));
+            }
+            flush_logs();
+            let _ = done.send(());
+        });
+        let flushed = finished.recv_timeout(Duration::from_secs(5));
+        drop(guard);
+        remove_sink(id);
+
+        assert!(flushed.is_ok(), "flush_logs bloqué après la panique d'un sink");
+        assert_eq!(written.load(Ordering::SeqCst), 4);
+    }
+}